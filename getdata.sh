@@ -6,8 +6,31 @@ pushd udata
 
 wget https://www.iana.org/assignments/language-subtag-registry/language-subtag-registry
 
+# Optional, for the ISO 639 crosswalk
+wget https://iso639-3.sil.org/sites/iso639-3/files/downloads/iso-639-3.tab
+wget https://iso639-3.sil.org/sites/iso639-3/files/downloads/iso-639-3-macrolanguages.tab
+wget https://www.loc.gov/standards/iso639-2/ISO-639-2_utf-8.txt
+
 wget ftp://unicode.org/Public/15.1.0/ucd/Blocks.txt
 wget ftp://unicode.org/Public/15.1.0/ucd/NamesList.txt
 wget ftp://unicode.org/Public/15.1.0/ucd/UnicodeData.txt
+wget ftp://unicode.org/Public/15.1.0/ucd/PropertyValueAliases.txt
+wget ftp://unicode.org/Public/15.1.0/ucd/Scripts.txt
+wget ftp://unicode.org/Public/15.1.0/ucd/ScriptExtensions.txt
+wget ftp://unicode.org/Public/15.1.0/ucd/DerivedAge.txt
+wget ftp://unicode.org/Public/15.1.0/ucd/PropList.txt
+wget ftp://unicode.org/Public/15.1.0/ucd/DerivedCoreProperties.txt
+wget ftp://unicode.org/Public/15.1.0/ucd/EastAsianWidth.txt
+wget ftp://unicode.org/Public/15.1.0/ucd/emoji/emoji-data.txt
+wget https://unicode.org/Public/emoji/15.1/emoji-sequences.txt
+wget https://unicode.org/Public/emoji/15.1/emoji-zwj-sequences.txt
+
+# The BCP 47 -u- and -t- extension data, from CLDR
+wget https://unicode.org/Public/cldr/44/core.zip
+unzip -j -o core.zip "common/bcp47/*" -d bcp47
+# The display names (explain --display-in)
+unzip -j -o core.zip "common/main/*" -d cldr/main
+unzip -j -o core.zip "common/supplemental/supplementalData.xml" "common/supplemental/likelySubtags.xml" -d cldr/supplemental
+rm core.zip
 
 popd
@@ -0,0 +1,179 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::IanaError;
+
+/// The folder (under `udata`) with the CLDR `common/bcp47/*.xml` files.
+pub const BCP47_DIR_NAME:&str = "bcp47";
+
+/// A value of a `-u-` key or `-t-` field, from the CLDR BCP 47 data.
+///
+/// Names in uppercase (`CODEPOINTS`, `REORDER_CODE`, `PRIVATE_USE`, ...)
+/// are not values but patterns the values have to follow.
+#[derive(Debug, Default)]
+pub struct Bcp47Type {
+    pub name: String,
+    pub description: String,
+    pub deprecated: bool,
+    pub preferred: Option<String>,
+    pub alias: Option<String>,
+}
+
+/// A `-u-` key or `-t-` field, with its possible values.
+#[derive(Debug, Default)]
+pub struct Bcp47Key {
+    /// `u` or `t`
+    pub extension: char,
+    pub name: String,
+    pub description: String,
+    pub deprecated: bool,
+    pub preferred: Option<String>,
+    pub alias: Option<String>,
+    pub types: Vec<Bcp47Type>,
+    /// The file the key was defined in (`calendar.xml`, `transform.xml`, ...).
+    pub file_name: String,
+}
+
+/// The keys and values of the Unicode locale and transform extensions.
+#[derive(Debug, Default)]
+pub struct Bcp47Data {
+    pub keys: Vec<Bcp47Key>,
+}
+
+impl Bcp47Type {
+    /// True for the uppercase names that are patterns for values, not values.
+    pub fn is_pattern(&self) -> bool {
+        self.name.chars().all(|c| c.is_ascii_uppercase() || c == '_')
+    }
+}
+
+fn all_subtags(value: &str, test: fn(&str) -> bool) -> bool {
+    value.split('-').all(test)
+}
+
+fn is_alphanum(subtag: &str, min: usize, max: usize) -> bool {
+    (min..=max).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+// True if the value follows the pattern, or if the pattern is one we don't know.
+fn matches_pattern(pattern: &str, value: &str, key: &Bcp47Key) -> bool {
+    match pattern {
+        "CODEPOINTS" => all_subtags(value, |s| (4..=6).contains(&s.len())
+                && s.chars().all(|c| c.is_ascii_hexdigit())),
+        "REORDER_CODE" => all_subtags(value, |s| s.len() == 4 && s.chars().all(|c| c.is_ascii_alphabetic()))
+                || value.split('-').all(|s| key.types.iter().any(|t| t.name == s)),
+        "SCRIPT_CODE" => all_subtags(value, |s| s.len() == 4 && s.chars().all(|c| c.is_ascii_alphabetic())),
+        "RG_KEY_VALUE" => value.len() == 6 && value.chars().all(|c| c.is_ascii_alphanumeric()),
+        "SUBDIVISION_CODE" => is_alphanum(value, 3, 8),
+        "PRIVATE_USE" => all_subtags(value, |s| is_alphanum(s, 3, 8)),
+        _ => true,
+    }
+}
+
+fn attribute(node: &roxmltree::Node, name: &str) -> Option<String> {
+    node.attribute(name).map(str::to_string)
+}
+
+impl Bcp47Key {
+    /// Finds the definition of a value: an exact match (or alias), or a pattern it follows.
+    pub fn find_type(&self, value: &str) -> Option<&Bcp47Type> {
+        let value = value.to_ascii_lowercase();
+        let exact = self.types.iter().find(|t| t.name.eq_ignore_ascii_case(&value)
+                || t.alias.as_ref().is_some_and(|alias| alias.split(' ').any(|a| a.eq_ignore_ascii_case(&value))));
+        exact.or_else(|| self.types.iter().find(|t| t.is_pattern() && matches_pattern(&t.name, &value, self)))
+    }
+}
+
+impl Bcp47Data {
+    /// Loads all the `*.xml` files in the folder.
+    pub fn load_dir(dir: &Path) -> Result<Bcp47Data, IanaError> {
+        let entries = fs::read_dir(dir).map_err(|source| IanaError::Io { path: dir.to_path_buf(), source })?;
+        let mut paths: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "xml"))
+                .collect();
+        paths.sort();
+        let mut result = Bcp47Data::default();
+        for path in paths {
+            let text = fs::read_to_string(&path).map_err(|source| IanaError::Io { path: path.clone(), source })?;
+            result.add_xml(&text, &path)?;
+        }
+        Ok(result)
+    }
+
+    /// Adds the keys from one CLDR `ldmlBCP47` file.
+    pub fn add_xml(&mut self, text: &str, path: &Path) -> Result<(), IanaError> {
+        let document = roxmltree::Document::parse(text)
+                .map_err(|err| IanaError::InvalidData { path: path.to_path_buf(), message: err.to_string() })?;
+        let file_name = path.file_name().map_or(String::new(), |name| name.to_string_lossy().to_string());
+        for key_node in document.descendants().filter(|node| node.has_tag_name("key")) {
+            let extension = key_node.attribute("extension").and_then(|ext| ext.chars().next()).unwrap_or('u');
+            let mut key = Bcp47Key {
+                extension,
+                name: attribute(&key_node, "name").unwrap_or_default().to_ascii_lowercase(),
+                description: attribute(&key_node, "description").unwrap_or_default(),
+                deprecated: key_node.attribute("deprecated") == Some("true"),
+                preferred: attribute(&key_node, "preferred"),
+                alias: attribute(&key_node, "alias"),
+                types: Vec::new(),
+                file_name: file_name.clone(),
+            };
+            for type_node in key_node.children().filter(|node| node.has_tag_name("type")) {
+                key.types.push(Bcp47Type {
+                    name: attribute(&type_node, "name").unwrap_or_default(),
+                    description: attribute(&type_node, "description").unwrap_or_default(),
+                    deprecated: type_node.attribute("deprecated") == Some("true"),
+                    preferred: attribute(&type_node, "preferred"),
+                    alias: attribute(&type_node, "alias"),
+                });
+            }
+            self.keys.push(key);
+        }
+        Ok(())
+    }
+
+    /// Finds a key of the `u` or `t` extension.
+    pub fn key(&self, extension: char, name: &str) -> Option<&Bcp47Key> {
+        self.keys.iter().find(|key| key.extension == extension && key.name.eq_ignore_ascii_case(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"<?xml version="1.0" encoding="UTF-8" ?>
+<ldmlBCP47>
+    <keyword>
+        <key name="ca" description="Calendar algorithm key" alias="calendar">
+            <type name="gregory" description="Gregorian calendar" alias="gregorian"/>
+            <type name="islamic-civil" description="Islamic calendar, tabular"/>
+            <type name="ethiopic-amete-alem" deprecated="true" preferred="ethioaa"/>
+        </key>
+        <key name="vt" description="Variable top" deprecated="true">
+            <type name="CODEPOINTS" description="The variable top"/>
+        </key>
+        <key extension="t" name="m0" description="Transform extension mechanism">
+            <type name="ungegn" description="United Nations Group of Experts on Geographical Names"/>
+        </key>
+    </keyword>
+</ldmlBCP47>
+"#;
+
+    #[test]
+    fn test_bcp47_data() {
+        let mut data = Bcp47Data::default();
+        data.add_xml(SAMPLE, Path::new("calendar.xml")).unwrap();
+        let ca = data.key('u', "ca").unwrap();
+        assert_eq!(ca.find_type("gregory").unwrap().description, "Gregorian calendar");
+        assert_eq!(ca.find_type("gregorian").unwrap().name, "gregory");
+        assert_eq!(ca.find_type("islamic-civil").unwrap().name, "islamic-civil");
+        assert!(ca.find_type("ethiopic-amete-alem").unwrap().deprecated);
+        assert!(ca.find_type("julian").is_none());
+        let vt = data.key('u', "vt").unwrap();
+        assert_eq!(vt.find_type("0020").unwrap().name, "CODEPOINTS");
+        assert!(vt.find_type("xyz").is_none());
+        assert!(data.key('u', "m0").is_none());
+        assert_eq!(data.key('t', "m0").unwrap().find_type("ungegn").unwrap().name, "ungegn");
+    }
+}
@@ -0,0 +1,332 @@
+//! The command line, described once: the help, the man page, the shell completions
+//! and the argument parsing are all driven by these tables.
+
+use std::str::FromStr;
+
+/// What an option does to the query or the output.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Action {
+    /// `Type` = the first value, and the value of the option is matched against the field (`Subtag` or `Tag`).
+    Shorthand(&'static str, &'static str),
+    /// The value of the option is matched against the field.
+    Field(&'static str),
+    /// `<name>=<value>`, for any field.
+    AnyField,
+    HasField,
+    MissingField,
+    Sort,
+    Limit,
+    Format,
+    Fields,
+    Saved,
+    Related,
+    DisplayIn,
+    Reverse,
+    ColorAlways,
+    ColorNever,
+    Fold,
+    Fuzzy,
+    Quiet,
+    Help,
+}
+
+/// Where the shell completion gets the values of an option from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Completion {
+    /// Free text, nothing to complete.
+    Text,
+    /// The `Subtag` (or `Tag`) of the registry records of the given type.
+    Subtags(&'static str),
+    /// All the distinct values of a field in the registry.
+    FieldValues(&'static str),
+    /// All the field names used in the registry.
+    FieldNames,
+    List(&'static [&'static str]),
+    /// The names of the queries saved in the config file.
+    SavedQueries,
+}
+
+/// How the records found are shown.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Format {
+    /// All the fields, like in the registry file.
+    #[default]
+    Full,
+    /// One line per record: `Latn (script: Latin)`.
+    Brief,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "full" => Ok(Format::Full),
+            "brief" => Ok(Format::Brief),
+            _ => Err(format!("Invalid format \"{}\", expected one of: {}", text, FORMATS.join(", "))),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Section {
+    Filtering,
+    Shorthands,
+    Other,
+    Ordering,
+    Output,
+}
+
+impl Section {
+    pub const ALL: [Section; 5] = [Section::Filtering, Section::Shorthands, Section::Other, Section::Ordering, Section::Output];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Section::Filtering => "Filtering",
+            Section::Shorthands => "Shorthands (for type=<option> & subtype / type=<value>)",
+            Section::Other => "Other",
+            Section::Ordering => "Ordering (the default is the file order)",
+            Section::Output => "Output",
+        }
+    }
+}
+
+pub struct OptionDef {
+    /// The short (`-l`) and long (`--language`) names.
+    pub names: &'static [&'static str],
+    /// The placeholder for the value (`<value>`), empty for flags.
+    pub value: &'static str,
+    pub help: &'static str,
+    pub section: Section,
+    pub action: Action,
+    pub completion: Completion,
+    /// Also accepted after a command (`iana_info check --color=never en-Latn`).
+    pub common: bool,
+}
+
+pub struct CommandDef {
+    pub name: &'static str,
+    /// The arguments, as shown in the usage line.
+    pub usage: &'static str,
+    pub help: &'static str,
+    /// Where the data comes from, shown on a line of its own.
+    pub note: &'static str,
+    /// Internal, not shown in the help and not offered by the shell completion.
+    pub hidden: bool,
+}
+
+// Exit codes: something matched, nothing matched, bad arguments, bad data file.
+pub const EXIT_MATCH:u8 = 0;
+pub const EXIT_NO_MATCH:u8 = 1;
+pub const EXIT_USAGE:u8 = 2;
+pub const EXIT_DATA_ERROR:u8 = 3;
+pub const EXIT_CODES: &[(u8, &str)] = &[
+    (EXIT_MATCH, "something matched"),
+    (EXIT_NO_MATCH, "nothing matched"),
+    (EXIT_USAGE, "usage error"),
+    (EXIT_DATA_ERROR, "data file error"),
+];
+
+pub const TYPES: &[&str] = &["extlang", "grandfathered", "language", "redundant", "region", "script", "variant"];
+pub const SORT_KEYS: &[&str] = &["subtag", "added", "deprecated", "description", "type"];
+pub const FORMATS: &[&str] = &["full", "brief"];
+pub const SHELLS: &[&str] = &["bash", "zsh", "fish"];
+
+const fn option(names: &'static [&'static str], value: &'static str, help: &'static str,
+        section: Section, action: Action, completion: Completion) -> OptionDef {
+    OptionDef { names, value, help, section, action, completion, common: false }
+}
+
+const fn field(names: &'static [&'static str], help: &'static str, name: &'static str, completion: Completion) -> OptionDef {
+    option(names, "<value>", help, Section::Filtering, Action::Field(name), completion)
+}
+
+const fn shorthand(names: &'static [&'static str], kind: &'static str, id: &'static str) -> OptionDef {
+    option(names, "<value>", "", Section::Shorthands, Action::Shorthand(kind, id), Completion::Subtags(kind))
+}
+
+const fn flag(names: &'static [&'static str], help: &'static str, section: Section, action: Action) -> OptionDef {
+    option(names, "", help, section, action, Completion::Text)
+}
+
+const fn common(def: OptionDef) -> OptionDef {
+    OptionDef { common: true, ..def }
+}
+
+pub const OPTIONS: &[OptionDef] = &[
+    field(&["-add", "--added"], "yyyy-MM-dd", "Added", Completion::Text),
+    field(&["-dep", "--deprecated"], "yyyy-MM-dd", "Deprecated", Completion::Text),
+    field(&["-cmt", "--comments"], "", "Comments", Completion::Text),
+    field(&["-d", "-desc", "--description"], "", "Description", Completion::Text),
+    field(&["-mac", "--macrolanguage"], "", "Macrolanguage", Completion::FieldValues("Macrolanguage")),
+    field(&["-pref", "--preferred-value"], "", "Preferred-Value", Completion::FieldValues("Preferred-Value")),
+    field(&["-px", "--prefix"], "", "Prefix", Completion::FieldValues("Prefix")),
+    field(&["-scp", "--scope"], "one of: collection, macrolanguage, private-use, special",
+            "Scope", Completion::FieldValues("Scope")),
+    field(&["-stg", "--subtag"], "", "Subtag", Completion::FieldValues("Subtag")),
+    field(&["-ss", "--suppress-script"], "", "Suppress-Script", Completion::FieldValues("Suppress-Script")),
+    field(&["-tg", "--tag"], "", "Tag", Completion::FieldValues("Tag")),
+    field(&["-t", "--type"], "one of: extlang, grandfathered, language, redundant, region, script, variant",
+            "Type", Completion::List(TYPES)),
+    option(&["--field"], "<name>=<value>", "any field, including ones not listed above",
+            Section::Filtering, Action::AnyField, Completion::Text),
+    option(&["--has-field"], "<name>", "records that have the field (e.g. Comments)",
+            Section::Filtering, Action::HasField, Completion::FieldNames),
+    option(&["--missing-field"], "<name>", "records that don't have the field",
+            Section::Filtering, Action::MissingField, Completion::FieldNames),
+
+    shorthand(&["-el", "--extlang"], "extlang", "Subtag"),
+    shorthand(&["-gf", "--grandfathered"], "grandfathered", "Tag"),
+    shorthand(&["-l", "--language"], "language", "Subtag"),
+    shorthand(&["-red", "--redundant"], "redundant", "Tag"),
+    shorthand(&["-r", "--region"], "region", "Subtag"),
+    shorthand(&["-s", "--script"], "script", "Subtag"),
+    shorthand(&["-v", "--variant"], "variant", "Subtag"),
+
+    common(flag(&["--color=always"], "force to always use colors", Section::Other, Action::ColorAlways)),
+    common(flag(&["--color=never"], "force to never use colors", Section::Other, Action::ColorNever)),
+    flag(&["--fold"], "case and accent insensitive matching (\"provencal\" finds \"Provençal\")",
+            Section::Other, Action::Fold),
+    flag(&["--fuzzy"], "list the records closest to the query (by edit distance), \
+            the exit code is still 1 if nothing matches exactly", Section::Other, Action::Fuzzy),
+    option(&["--saved"], "<name>", "the arguments of a query saved in the config file",
+            Section::Other, Action::Saved, Completion::SavedQueries),
+    common(flag(&["-q", "--quiet"], "don't show the File-Date line and the DONE! footer", Section::Other, Action::Quiet)),
+    common(flag(&["-h", "--help"], "this help", Section::Other, Action::Help)),
+
+    option(&["--sort"], "<key>", "one of: subtag, added, deprecated, description, type",
+            Section::Ordering, Action::Sort, Completion::List(SORT_KEYS)),
+    flag(&["--reverse"], "reverse the order (records without the sort field stay last)",
+            Section::Ordering, Action::Reverse),
+    option(&["--limit"], "<count>", "show at most <count> records", Section::Ordering, Action::Limit, Completion::Text),
+
+    option(&["--format"], "<format>", "one of: full (all the fields, the default), brief (one line per record)",
+            Section::Output, Action::Format, Completion::List(FORMATS)),
+    option(&["--fields"], "<name>,...", "only show these fields (full format)",
+            Section::Output, Action::Fields, Completion::FieldNames),
+    common(option(&["--display-in"], "<locale>", "with explain, also show the name of the tag in that language \
+            (e.g. fr, from the CLDR main/*.xml files in udata/cldr)", Section::Output, Action::DisplayIn, Completion::Text)),
+    flag(&["--related"], "also show the records referenced by Preferred-Value, Macrolanguage, Suppress-Script \
+            and Prefix, and the records with a Preferred-Value pointing back", Section::Output, Action::Related),
+];
+
+pub const COMMANDS: &[CommandDef] = &[
+    CommandDef { name: "compose", usage: "<language> [<script>] [<region>]", hidden: false,
+            help: "build a tag from English names (\"Serbian\" \"Latin\" \"Serbia\" => sr-Latn-RS)", note: "" },
+    CommandDef { name: "check", usage: "<tag> ...", hidden: false,
+            help: "best practice warnings (Suppress-Script, extlang, deprecated subtags, ...)", note: "" },
+    CommandDef { name: "explain", usage: "<tag> ...", hidden: false,
+            help: "what each subtag means, including the -u- and -t- keys and values",
+            note: "the -u- / -t- data is read from the CLDR bcp47/*.xml files in udata/bcp47" },
+    CommandDef { name: "iso639", usage: "<code> ...", hidden: false,
+            help: "any ISO 639-1, 639-2/B, 639-2/T or 639-3 code to BCP 47 (fre => fr), and back",
+            note: "from iso-639-3.tab, iso-639-3-macrolanguages.tab and ISO-639-2_utf-8.txt in udata" },
+    CommandDef { name: "maximize", usage: "<tag> ...", hidden: false,
+            help: "add the likely script and region (zh-TW => zh-Hant-TW)",
+            note: "from the CLDR supplemental/likelySubtags.xml file in udata/cldr" },
+    CommandDef { name: "minimize", usage: "<tag> ...", hidden: false,
+            help: "remove the subtags maximize would add back (en-Latn-US => en)", note: "" },
+    CommandDef { name: "completions", usage: "bash|zsh|fish", hidden: false,
+            help: "print a completion script for the shell (e.g. source <(iana_info completions bash))", note: "" },
+    CommandDef { name: "man", usage: "", hidden: false,
+            help: "print the man page (e.g. iana_info man > ~/.local/share/man/man1/iana_info.1)", note: "" },
+    CommandDef { name: "complete", usage: "<option> <prefix>", hidden: true,
+            help: "the values of an option that start with the prefix (used by the completion scripts)", note: "" },
+];
+
+impl OptionDef {
+    pub fn short_names(&self) -> impl Iterator<Item = &'static str> {
+        self.names.iter().copied().filter(|name| !name.starts_with("--"))
+    }
+
+    pub fn long_name(&self) -> &'static str {
+        self.names.iter().copied().find(|name| name.starts_with("--")).unwrap_or_default()
+    }
+
+    pub fn takes_value(&self) -> bool {
+        !self.value.is_empty()
+    }
+
+    /// What the help shows after the names (the `<value>` placeholder has its own column).
+    pub fn description(&self) -> String {
+        match self.action {
+            Action::Shorthand(kind, id) => format!("Type={} and {}=<value>", kind, id),
+            Action::Field(name) if self.help.is_empty() => format!("records whose {} contains <value>", name),
+            Action::Field(name) => format!("records whose {} contains <value> ({})", name, self.help),
+            _ => self.help.to_string(),
+        }
+    }
+}
+
+pub fn find_option(name: &str) -> Option<&'static OptionDef> {
+    OPTIONS.iter().find(|def| def.names.contains(&name))
+}
+
+pub fn find_command(name: &str) -> Option<&'static CommandDef> {
+    COMMANDS.iter().find(|def| def.name == name)
+}
+
+/// The `--help` text.
+pub fn help_text() -> String {
+    let mut result = String::new();
+    result += "Usage: iana_info --key <value> [--key <value>] ...\n";
+    for command in COMMANDS.iter().filter(|command| !command.hidden) {
+        result += format!("       iana_info {} {}", command.name, command.usage).trim_end();
+        result += "\n";
+    }
+    result += "\nFind info in the IANA Language Subtag Registry\n";
+    result += "Commands:\n";
+    for command in COMMANDS.iter().filter(|command| !command.hidden) {
+        result += &format!("  {:<12} : {}\n", command.name, command.help);
+        if !command.note.is_empty() {
+            result += &format!("      ({})\n", command.note);
+        }
+    }
+    for section in Section::ALL {
+        result += &format!("{}:\n", section.title());
+        for def in OPTIONS.iter().filter(|def| def.section == section) {
+            let shorts: Vec<&str> = def.short_names().collect();
+            let line = format!("  {:<8} {:<17} {:<15}", shorts.join(" "), def.long_name(), def.value);
+            let description = def.description();
+            if description.is_empty() {
+                result += line.trim_end();
+            } else {
+                result += &format!("{} : {}", line, description);
+            }
+            result += "\n";
+        }
+    }
+    result += "where the value can be a substring or exact match if it starts with '='\n";
+    result += "Matching is case insensitive by default, and also accent insensitive with --fold.\n";
+    result += "Defaults, aliases and saved queries are read from ~/.config/iana_info/config.toml\n";
+    let codes: Vec<String> = EXIT_CODES.iter().map(|(code, meaning)| format!("{} {}", code, meaning)).collect();
+    result += &format!("Exit codes: {}\n", codes.join(", "));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options() {
+        let mut names: Vec<&str> = OPTIONS.iter().flat_map(|def| def.names.iter().copied()).collect();
+        let count = names.len();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), count, "an option name is used twice");
+        assert!(names.iter().all(|name| name.starts_with('-')));
+        assert!(OPTIONS.iter().all(|def| def.short_names().count() > 0 || !def.long_name().is_empty()));
+        assert_eq!(find_option("--language").unwrap().action, Action::Shorthand("language", "Subtag"));
+        assert_eq!(find_option("-desc").unwrap().action, Action::Field("Description"));
+        assert!(find_option("--nope").is_none());
+
+        let help = help_text();
+        for def in OPTIONS {
+            assert!(def.names.iter().all(|name| help.contains(name)));
+        }
+        for command in COMMANDS.iter().filter(|command| !command.hidden) {
+            assert!(help.contains(command.name));
+        }
+    }
+}
@@ -0,0 +1,257 @@
+//! Shell completion scripts and the man page, generated from the tables in `cli`.
+
+use iana_info::Registry;
+
+use crate::cli::{self, Completion, Section, COMMANDS, EXIT_CODES, OPTIONS};
+use crate::config::Config;
+
+// The release scripts install the binary as `ii` too.
+const PROGRAMS: &[&str] = &["iana_info", "ii"];
+
+fn visible_commands() -> impl Iterator<Item = &'static cli::CommandDef> {
+    COMMANDS.iter().filter(|command| !command.hidden)
+}
+
+fn value_option_names() -> Vec<&'static str> {
+    OPTIONS.iter().filter(|def| def.takes_value()).flat_map(|def| def.names.iter().copied()).collect()
+}
+
+// For single quoted shell strings.
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+/// The values of an option starting with the prefix (ignoring case), for the completion scripts.
+/// A leading `=` (exact match) is kept.
+pub fn complete_values(registry: &Registry, config: &Config, completion: Completion, prefix: &str) -> Vec<String> {
+    let (exact, prefix) = match prefix.strip_prefix('=') {
+        Some(rest) => ("=", rest.to_lowercase()),
+        None => ("", prefix.to_lowercase()),
+    };
+    let mut result: Vec<String> = match completion {
+        Completion::Text => Vec::new(),
+        Completion::Subtags(kind) => registry.records.iter()
+            .filter(|record| record.kind() == kind)
+            .map(|record| record.id().to_string())
+            .collect(),
+        Completion::FieldValues(name) => registry.records.iter()
+            .flat_map(|record| record.values(name))
+            .map(str::to_string)
+            .collect(),
+        Completion::FieldNames => registry.records.iter()
+            .flat_map(|record| record.fields().map(|(name, _)| name.to_string()))
+            .collect(),
+        Completion::List(list) => list.iter().map(|value| value.to_string()).collect(),
+        Completion::SavedQueries => config.queries.iter().map(|(name, _)| name.to_string()).collect(),
+    };
+    result.retain(|value| value.to_lowercase().starts_with(&prefix));
+    result.sort();
+    result.dedup();
+    result.into_iter().map(|value| format!("{}{}", exact, value)).collect()
+}
+
+pub fn bash() -> String {
+    let commands: Vec<&str> = visible_commands().map(|command| command.name).collect();
+    let all_options: Vec<&str> = OPTIONS.iter().flat_map(|def| def.names.iter().copied()).collect();
+    let common_options: Vec<&str> = OPTIONS.iter()
+        .filter(|def| def.common)
+        .flat_map(|def| def.names.iter().copied())
+        .collect();
+
+    let mut result = String::new();
+    result += "_iana_info() {\n";
+    result += "    local cur=\"${COMP_WORDS[COMP_CWORD]}\"\n";
+    result += "    local prev=\"${COMP_WORDS[COMP_CWORD-1]}\"\n";
+    result += "    case \"$prev\" in\n";
+    result += &format!("        {})\n", value_option_names().join("|"));
+    result += "            local IFS=$'\\n'\n";
+    result += "            COMPREPLY=($(\"${COMP_WORDS[0]}\" complete \"$prev\" \"$cur\" 2>/dev/null))\n";
+    result += "            return ;;\n";
+    result += "        completions)\n";
+    result += &format!("            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n", cli::SHELLS.join(" "));
+    result += "            return ;;\n";
+    result += "    esac\n";
+    result += "    if [[ $COMP_CWORD -eq 1 && \"$cur\" != -* ]]; then\n";
+    result += &format!("        COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n", commands.join(" "));
+    result += "        return\n";
+    result += "    fi\n";
+    result += "    case \"${COMP_WORDS[1]}\" in\n";
+    result += &format!("        {})\n", commands.join("|"));
+    result += &format!("            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")) ;;\n", common_options.join(" "));
+    result += "        *)\n";
+    result += &format!("            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")) ;;\n", all_options.join(" "));
+    result += "    esac\n";
+    result += "}\n";
+    result += &format!("complete -F _iana_info {}\n", PROGRAMS.join(" "));
+    result
+}
+
+pub fn zsh() -> String {
+    let mut result = String::new();
+    result += &format!("#compdef {}\n\n", PROGRAMS.join(" "));
+    result += "_iana_info() {\n";
+    result += "    local -a options commands values\n";
+    result += "    options=(\n";
+    for def in OPTIONS {
+        for name in def.names {
+            result += &format!("        {}\n", quote(&format!("{}:{}", name.replace(':', "\\:"), def.description())));
+        }
+    }
+    result += "    )\n";
+    result += "    commands=(\n";
+    for command in visible_commands() {
+        result += &format!("        {}\n", quote(&format!("{}:{}", command.name, command.help)));
+    }
+    result += "    )\n";
+    result += "    case ${words[CURRENT-1]} in\n";
+    result += &format!("        ({})\n", value_option_names().join("|"));
+    result += "            values=(${(f)\"$(${words[1]} complete ${words[CURRENT-1]} ${words[CURRENT]} 2>/dev/null)\"})\n";
+    result += "            compadd -U -a values\n";
+    result += "            return ;;\n";
+    result += "        (completions)\n";
+    result += &format!("            compadd {}\n", cli::SHELLS.join(" "));
+    result += "            return ;;\n";
+    result += "    esac\n";
+    result += "    if (( CURRENT == 2 )) && [[ ${words[CURRENT]} != -* ]]; then\n";
+    result += "        _describe 'command' commands\n";
+    result += "    else\n";
+    result += "        _describe 'option' options\n";
+    result += "    fi\n";
+    result += "}\n\n";
+    result += "if [ \"$funcstack[1]\" = \"_iana_info\" ]; then\n";
+    result += "    _iana_info \"$@\"\n";
+    result += "else\n";
+    result += &format!("    compdef _iana_info {}\n", PROGRAMS.join(" "));
+    result += "fi\n";
+    result
+}
+
+pub fn fish() -> String {
+    let mut result = String::new();
+    result += "function __iana_info_values\n";
+    result += "    set -l cmd (commandline -opc)\n";
+    result += "    $cmd[1] complete $argv[1] (commandline -ct) 2>/dev/null\n";
+    result += "end\n\n";
+    result += "complete -c iana_info -f\n";
+    for command in visible_commands() {
+        result += &format!("complete -c iana_info -n __fish_use_subcommand -a {} -d {}\n",
+                command.name, quote(command.help));
+    }
+    result += &format!("complete -c iana_info -n '__fish_seen_subcommand_from completions' -a {}\n",
+            quote(&cli::SHELLS.join(" ")));
+    for def in OPTIONS {
+        let mut line = "complete -c iana_info".to_string();
+        for name in def.short_names() {
+            let name = &name[1..];
+            line += &format!(" -{} {}", if name.len() == 1 { "s" } else { "o" }, name);
+        }
+        let long = def.long_name();
+        if let Some((name, value)) = long.split_once('=') {
+            // --color=always and --color=never
+            line += &format!(" -l {} -x -a {}", &name[2..], value);
+        } else if !long.is_empty() {
+            line += &format!(" -l {}", &long[2..]);
+        }
+        if def.takes_value() {
+            let name = def.names[0];
+            line += &format!(" -x -a {}", quote(&format!("(__iana_info_values {})", name)));
+        }
+        line += &format!(" -d {}", quote(&def.description()));
+        result += &line;
+        result += "\n";
+    }
+    for program in PROGRAMS.iter().skip(1) {
+        result += &format!("complete -c {} -w iana_info\n", program);
+    }
+    result
+}
+
+// Escapes text for roff.
+fn roff(text: &str) -> String {
+    let escaped = text.replace('\\', "\\\\").replace('-', "\\-");
+    if escaped.starts_with('.') || escaped.starts_with('\'') {
+        format!("\\&{}", escaped)
+    } else {
+        escaped
+    }
+}
+
+pub fn man() -> String {
+    let mut result = String::new();
+    result += &format!(".TH IANA_INFO 1 \"\" \"iana_info {}\" \"User Commands\"\n", env!("CARGO_PKG_VERSION"));
+    result += ".SH NAME\n";
+    result += "iana_info \\- find info in the IANA Language Subtag Registry\n";
+    result += ".SH SYNOPSIS\n";
+    result += ".B iana_info\n";
+    result += "\\fB\\-\\-key\\fR \\fI<value>\\fR [\\fB\\-\\-key\\fR \\fI<value>\\fR] ...\n";
+    for command in visible_commands() {
+        result += ".br\n";
+        result += &format!(".B iana_info {}\n", command.name);
+        if !command.usage.is_empty() {
+            result += &format!("\\fI{}\\fR\n", roff(command.usage));
+        }
+    }
+    result += ".SH DESCRIPTION\n";
+    result += "Searches the records of the IANA Language Subtag Registry.\n";
+    result += "The value of an option can be a substring, or an exact match if it starts with '='.\n";
+    result += "Matching is case insensitive by default, and also accent insensitive with \\fB\\-\\-fold\\fR.\n";
+    result += ".SH COMMANDS\n";
+    for command in visible_commands() {
+        result += ".TP\n";
+        result += &format!("\\fB{}\\fR \\fI{}\\fR\n", command.name, roff(command.usage));
+        result += &format!("{}\n", roff(command.help));
+        if !command.note.is_empty() {
+            result += &format!("({})\n", roff(command.note));
+        }
+    }
+    result += ".SH OPTIONS\n";
+    for section in Section::ALL {
+        result += &format!(".SS {}\n", roff(section.title()));
+        for def in OPTIONS.iter().filter(|def| def.section == section) {
+            let names: Vec<String> = def.names.iter().map(|name| format!("\\fB{}\\fR", roff(name))).collect();
+            result += ".TP\n";
+            if def.takes_value() {
+                result += &format!("{} \\fI{}\\fR\n", names.join(", "), roff(def.value));
+            } else {
+                result += &format!("{}\n", names.join(", "));
+            }
+            let description = def.description();
+            if !description.is_empty() {
+                result += &format!("{}\n", roff(&description));
+            }
+        }
+    }
+    result += ".SH EXIT STATUS\n";
+    for (code, meaning) in EXIT_CODES {
+        result += &format!(".TP\n{}\n{}\n", code, meaning);
+    }
+    result += ".SH FILES\n";
+    result += "The data files are read from the \\fIudata\\fR folder next to the executable\n";
+    result += "(or the \\fBdata\\-dir\\fR of the config file):\n";
+    result += "\\fIlanguage\\-subtag\\-registry\\fR, the optional ISO 639 tables,\n";
+    result += "and the CLDR \\fIbcp47/*.xml\\fR files.\n";
+    result += ".TP\n";
+    result += "\\fI~/.config/iana_info/config.toml\\fR\n";
+    result += "The defaults (\\fBdata\\-dir\\fR, \\fBcolor\\fR, \\fBformat\\fR, \\fBfields\\fR),\n";
+    result += "the \\fB[aliases]\\fR shorthands and the \\fB[queries]\\fR used by \\fB\\-\\-saved\\fR.\n";
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_complete_values() {
+        let config = Config::parse("[queries]\nnew = \"--sort added\"").unwrap();
+        let registry = Registry::parse("%%\nType: script\nSubtag: Latn\n%%\nType: script\nSubtag: Laoo\n\
+                %%\nType: language\nSubtag: la\nScope: macrolanguage\n".as_bytes(), Path::new("sample")).unwrap();
+        assert_eq!(complete_values(&registry, &config, Completion::Subtags("script"), "la"), vec!["Laoo", "Latn"]);
+        assert_eq!(complete_values(&registry, &config, Completion::Subtags("script"), "=Lat"), vec!["=Latn"]);
+        assert_eq!(complete_values(&registry, &config, Completion::FieldValues("Scope"), ""), vec!["macrolanguage"]);
+        assert_eq!(complete_values(&registry, &config, Completion::FieldNames, "s"), vec!["Scope", "Subtag"]);
+        assert_eq!(complete_values(&registry, &config, Completion::SavedQueries, "n"), vec!["new"]);
+        assert!(complete_values(&registry, &config, Completion::Text, "").is_empty());
+    }
+}
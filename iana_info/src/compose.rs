@@ -0,0 +1,147 @@
+use crate::fold;
+use crate::record::Record;
+use crate::registry::Registry;
+
+/// The outcome of resolving one natural-language name to a subtag.
+pub enum Resolved<'a> {
+    Found(&'a Record),
+    NotFound,
+    Ambiguous(Vec<&'a Record>),
+}
+
+// Lowercase words separated by single spaces, padded with a space on both sides,
+// so that a containment test only matches whole words.
+fn words(text: &str) -> String {
+    let mut result = " ".to_string();
+    for word in fold::fold(text).split(|c: char| !c.is_alphanumeric()) {
+        if !word.is_empty() {
+            result = result + word + " ";
+        }
+    }
+    result
+}
+
+fn has_description(record: &Record, name: &str, exact: bool) -> bool {
+    let name = words(name);
+    record.values("Description").iter().any(|description| {
+        let description = words(description);
+        if exact {
+            description == name
+        } else {
+            description.contains(&name)
+        }
+    })
+}
+
+// Exact description matches win over partial (whole word) matches,
+// and non-deprecated records win over deprecated ones.
+fn find_candidates<'a>(registry: &'a Registry, name: &str, types: &[&str]) -> Vec<&'a Record> {
+    for exact in [true, false] {
+        let candidates: Vec<&Record> = registry.records.iter()
+            .filter(|record| types.contains(&record.kind()))
+            .filter(|record| has_description(record, name, exact))
+            .collect();
+        if candidates.is_empty() {
+            continue;
+        }
+        let current: Vec<&Record> = candidates.iter()
+            .filter(|record| !record.is_deprecated())
+            .copied()
+            .collect();
+        return if current.is_empty() { candidates } else { current };
+    }
+    Vec::new()
+}
+
+/// Resolves a name against the descriptions of the records with one of the given types.
+pub fn resolve<'a>(registry: &'a Registry, name: &str, types: &[&str]) -> Resolved<'a> {
+    let candidates = find_candidates(registry, name, types);
+    match candidates.len() {
+        0 => Resolved::NotFound,
+        1 => Resolved::Found(candidates[0]),
+        _ => Resolved::Ambiguous(candidates),
+    }
+}
+
+/// Builds a tag from a language name followed by optional script and region names.
+///
+/// `compose(registry, &["Serbian", "Latin", "Serbia"])` returns `sr-Latn-RS`.
+/// The script is dropped when it is the `Suppress-Script` of the language.
+pub fn compose(registry: &Registry, names: &[String]) -> Result<String, String> {
+    let (language_name, rest) = match names.split_first() {
+        Some(split) => split,
+        None => return Err("Nothing to compose, expected a language name".to_string()),
+    };
+
+    let language = resolve_or_explain(registry, language_name, &["language"])?;
+    let mut script: Option<&Record> = None;
+    let mut region: Option<&Record> = None;
+    for name in rest {
+        let record = resolve_or_explain(registry, name, &["script", "region"])?;
+        let slot = if record.kind() == "script" { &mut script } else { &mut region };
+        if let Some(previous) = slot {
+            return Err(format!("Both \"{}\" and \"{}\" are of type {}",
+                    previous.first_description(), name, record.kind()));
+        }
+        *slot = Some(record);
+    }
+
+    let mut result = language.field("Subtag").to_string();
+    if let Some(script) = script {
+        let subtag = script.field("Subtag");
+        if !language.field("Suppress-Script").eq_ignore_ascii_case(subtag) {
+            result = result + "-" + subtag;
+        }
+    }
+    if let Some(region) = region {
+        result = result + "-" + region.field("Subtag");
+    }
+    Ok(result)
+}
+
+fn resolve_or_explain<'a>(registry: &'a Registry, name: &str, types: &[&str]) -> Result<&'a Record, String> {
+    match resolve(registry, name, types) {
+        Resolved::Found(record) => Ok(record),
+        Resolved::NotFound => Err(format!("No {} named \"{}\"", types.join(" or "), name)),
+        Resolved::Ambiguous(candidates) => {
+            let mut message = format!("\"{}\" is ambiguous, candidates:", name);
+            for candidate in candidates {
+                message = message + &format!("\n  {} ({}: {})",
+                        candidate.field("Subtag"), candidate.kind(), candidate.first_description());
+            }
+            Err(message)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(fields: &[(&str, &str)]) -> Record {
+        fields.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_compose() {
+        let registry = Registry { file_date: String::new(), records: vec![
+            record(&[("Type", "language"), ("Subtag", "sr"), ("Description", "Serbian"), ("Suppress-Script", "Cyrl")]),
+            record(&[("Type", "language"), ("Subtag", "en"), ("Description", "English"), ("Suppress-Script", "Latn")]),
+            record(&[("Type", "language"), ("Subtag", "la"), ("Description", "Latin")]),
+            record(&[("Type", "script"), ("Subtag", "Latn"), ("Description", "Latin")]),
+            record(&[("Type", "region"), ("Subtag", "RS"), ("Description", "Serbia")]),
+            record(&[("Type", "region"), ("Subtag", "CS"), ("Description", "Serbia and Montenegro"), ("Deprecated", "2006-10-05")]),
+            record(&[("Type", "region"), ("Subtag", "US"), ("Description", "United States")]),
+        ] };
+        assert_eq!(compose(&registry, &names(&["Serbian", "Latin", "Serbia"])), Ok("sr-Latn-RS".to_string()));
+        assert_eq!(compose(&registry, &names(&["english", "latin"])), Ok("en".to_string()));
+        assert_eq!(compose(&registry, &names(&["English", "United States"])), Ok("en-US".to_string()));
+        assert_eq!(compose(&registry, &names(&["Latin"])), Ok("la".to_string()));
+        assert!(compose(&registry, &names(&["Klingon"])).is_err());
+        assert!(compose(&registry, &names(&[])).is_err());
+    }
+}
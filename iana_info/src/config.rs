@@ -0,0 +1,177 @@
+//! The per-user configuration file, `~/.config/iana_info/config.toml`:
+//!
+//! ```toml
+//! data-dir = "/usr/share/iana_info"   # instead of the udata folder next to the executable
+//! color = "never"                     # auto, always, never
+//! format = "brief"                    # full, brief
+//! fields = ["Subtag", "Description"]  # the fields shown by the full format
+//!
+//! [aliases]
+//! # -lang <value> is the same as --type =language --subtag <value>
+//! -lang = { match = { Type = "=language" }, field = "Subtag" }
+//! # a flag, without a value
+//! --macro = { match = { Scope = "=macrolanguage" } }
+//!
+//! [queries]
+//! # iana_info --saved new-scripts
+//! new-scripts = "-t =script --sort added --reverse --limit 10"
+//! ```
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use iana_info::IanaError;
+
+use crate::cli::Format;
+
+pub const CONFIG_FILE_NAME:&str = "config.toml";
+
+/// A user defined shorthand. The built-in ones (`-l`, `-s`, ...) are in the option table,
+/// an alias with the same name replaces them.
+#[derive(Debug, Default, PartialEq)]
+pub struct Alias {
+    pub name: String,
+    /// Added to the query as they are (a value starting with `=` is an exact match).
+    pub criteria: Vec<(String, String)>,
+    /// The field the value following the alias is matched against, `None` for flags.
+    pub field: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct Config {
+    pub data_dir: Option<PathBuf>,
+    /// `None` is auto (colors if the output is a terminal).
+    pub use_colors: Option<bool>,
+    pub format: Option<Format>,
+    pub fields: Vec<String>,
+    pub aliases: Vec<Alias>,
+    /// Named lists of arguments, used with `--saved <name>`.
+    pub queries: Vec<(String, Vec<String>)>,
+}
+
+/// `$XDG_CONFIG_HOME/iana_info/config.toml` (or `~/.config/...`), `%APPDATA%\iana_info\config.toml` on Windows.
+pub fn default_config_path() -> Option<PathBuf> {
+    let config_dir = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        env::var_os("XDG_CONFIG_HOME").map(PathBuf::from)
+                .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+    };
+    config_dir.map(|dir| dir.join("iana_info").join(CONFIG_FILE_NAME))
+}
+
+fn string(value: &toml::Value, key: &str) -> Result<String, String> {
+    value.as_str().map(str::to_string).ok_or_else(|| format!("{} should be a string", key))
+}
+
+fn strings(value: &toml::Value, key: &str) -> Result<Vec<String>, String> {
+    match value {
+        toml::Value::String(text) => Ok(text.split_whitespace().map(str::to_string).collect()),
+        toml::Value::Array(items) => items.iter().map(|item| string(item, key)).collect(),
+        _ => Err(format!("{} should be a string or an array of strings", key)),
+    }
+}
+
+fn table<'a>(value: &'a toml::Value, key: &str) -> Result<&'a toml::Table, String> {
+    value.as_table().ok_or_else(|| format!("{} should be a table", key))
+}
+
+fn parse_alias(name: &str, value: &toml::Value) -> Result<Alias, String> {
+    let key = format!("aliases.{}", name);
+    if !name.starts_with('-') {
+        return Err(format!("{}: an alias should start with '-'", key));
+    }
+    let mut alias = Alias { name: name.to_string(), ..Default::default() };
+    for (field, value) in table(value, &key)? {
+        match field.as_str() {
+            "match" => for (name, criterion) in table(value, &format!("{}.match", key))? {
+                alias.criteria.push((name.to_string(), string(criterion, &format!("{}.match.{}", key, name))?));
+            },
+            "field" => alias.field = Some(string(value, &format!("{}.field", key))?),
+            _ => return Err(format!("{}: unknown key \"{}\"", key, field)),
+        }
+    }
+    if alias.criteria.is_empty() && alias.field.is_none() {
+        return Err(format!("{}: needs a match or a field", key));
+    }
+    Ok(alias)
+}
+
+impl Config {
+    /// Loads the file, a missing file is the same as an empty one.
+    pub fn load(path: &Path) -> Result<Config, IanaError> {
+        if !path.is_file() {
+            return Ok(Config::default());
+        }
+        let text = fs::read_to_string(path).map_err(|source| IanaError::Io { path: path.to_path_buf(), source })?;
+        Config::parse(&text).map_err(|message| IanaError::InvalidData { path: path.to_path_buf(), message })
+    }
+
+    pub fn parse(text: &str) -> Result<Config, String> {
+        let root: toml::Table = text.parse().map_err(|err: toml::de::Error| err.message().to_string())?;
+        let mut config = Config::default();
+        for (key, value) in &root {
+            match key.as_str() {
+                "data-dir" => config.data_dir = Some(PathBuf::from(string(value, key)?)),
+                "color" => config.use_colors = match string(value, key)?.as_str() {
+                    "auto" => None,
+                    "always" => Some(true),
+                    "never" => Some(false),
+                    other => return Err(format!("color should be one of: auto, always, never, not \"{}\"", other)),
+                },
+                "format" => config.format = Some(string(value, key)?.parse()?),
+                "fields" => config.fields = strings(value, key)?,
+                "aliases" => for (name, alias) in table(value, key)? {
+                    config.aliases.push(parse_alias(name, alias)?);
+                },
+                "queries" => for (name, query) in table(value, key)? {
+                    config.queries.push((name.to_string(), strings(query, &format!("queries.{}", name))?));
+                },
+                _ => return Err(format!("unknown key \"{}\"", key)),
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn find_alias(&self, name: &str) -> Option<&Alias> {
+        self.aliases.iter().find(|alias| alias.name == name)
+    }
+
+    pub fn find_query(&self, name: &str) -> Option<&[String]> {
+        self.queries.iter().find(|(query, _)| query == name).map(|(_, args)| args.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(r#"
+            color = "never"
+            format = "brief"
+            fields = ["Subtag", "Description"]
+            [aliases]
+            -lang = { match = { Type = "=language" }, field = "Subtag" }
+            --macro = { match = { Scope = "=macrolanguage" } }
+            [queries]
+            new-scripts = "-t =script --sort added"
+            by-list = ["-d", "Old Church"]
+        "#).unwrap();
+        assert_eq!(config.use_colors, Some(false));
+        assert_eq!(config.format, Some(Format::Brief));
+        assert_eq!(config.fields, vec!["Subtag", "Description"]);
+        let lang = config.find_alias("-lang").unwrap();
+        assert_eq!(lang.criteria, vec![("Type".to_string(), "=language".to_string())]);
+        assert_eq!(lang.field.as_deref(), Some("Subtag"));
+        assert!(config.find_alias("--macro").unwrap().field.is_none());
+        assert_eq!(config.find_query("new-scripts").unwrap(), ["-t", "=script", "--sort", "added"]);
+        assert_eq!(config.find_query("by-list").unwrap(), ["-d", "Old Church"]);
+
+        assert!(Config::parse("colour = \"never\"").is_err());
+        assert!(Config::parse("color = \"sometimes\"").is_err());
+        assert!(Config::parse("[aliases]\nlang = { field = \"Subtag\" }").is_err());
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::error::IanaError;
+use crate::tag::LanguageTag;
+
+/// The folder (under `udata`) with the CLDR `common/main/*.xml` and `common/supplemental/*.xml` files.
+pub const CLDR_DIR_NAME:&str = "cldr";
+
+/// Localized names of languages, scripts, territories and variants, from the CLDR locale data.
+#[derive(Debug, Default)]
+pub struct DisplayNames {
+    /// The CLDR files used, from the most specific locale to `root`.
+    pub locales: Vec<String>,
+    // Keyed by the lowercase code, the first (most specific) locale wins.
+    languages: BTreeMap<String, String>,
+    scripts: BTreeMap<String, String>,
+    territories: BTreeMap<String, String>,
+    variants: BTreeMap<String, String>,
+    /// `{0} ({1})`
+    pattern: Option<String>,
+    /// `{0}, {1}`
+    separator: Option<String>,
+}
+
+/// The explicit parents (`es_AR` => `es_419`) from a CLDR `supplementalData.xml` file.
+pub fn parse_parent_locales(text: &str, path: &Path) -> Result<BTreeMap<String, String>, IanaError> {
+    let document = roxmltree::Document::parse(text)
+            .map_err(|err| IanaError::InvalidData { path: path.to_path_buf(), message: err.to_string() })?;
+    let mut result: BTreeMap<String, String> = BTreeMap::new();
+    for node in document.descendants().filter(|node| node.has_tag_name("parentLocale")) {
+        // There are other kinds of parents (collations, segmentations), only the default one matters here.
+        if node.parent().is_some_and(|parent| parent.attribute("component").is_some()) {
+            continue;
+        }
+        let (Some(parent), Some(locales)) = (node.attribute("parent"), node.attribute("locales")) else {
+            continue;
+        };
+        for locale in locales.split_whitespace() {
+            result.insert(locale.to_string(), parent.to_string());
+        }
+    }
+    Ok(result)
+}
+
+/// The locale and its parents, in CLDR file naming (`de-CH` => `de_CH`, `de`, `root`).
+pub fn parent_chain(locale: &str, parents: &BTreeMap<String, String>) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+    let mut current = locale.replace('-', "_");
+    while !current.is_empty() && current != "root" && !result.contains(&current) {
+        result.push(current.clone());
+        current = match parents.get(&current) {
+            Some(parent) => parent.clone(),
+            None => current.rsplit_once('_').map_or(String::new(), |(parent, _)| parent.to_string()),
+        };
+    }
+    result.push("root".to_string());
+    result
+}
+
+fn fill(map: &mut BTreeMap<String, String>, parent: roxmltree::Node, element: &str) {
+    for node in parent.children().filter(|node| node.has_tag_name(element)) {
+        // alt="short", alt="variant", ... are not the default names
+        if node.attribute("alt").is_some() {
+            continue;
+        }
+        if let (Some(code), Some(name)) = (node.attribute("type"), node.text()) {
+            map.entry(code.to_lowercase()).or_insert_with(|| name.to_string());
+        }
+    }
+}
+
+fn format_pattern(pattern: &str, first: &str, second: &str) -> String {
+    pattern.replace("{0}", first).replace("{1}", second)
+}
+
+impl DisplayNames {
+    /// Loads the names for a locale (`fr`, `fr-CA`, `zh-Hant`), falling back along the parent chain.
+    pub fn load(cldr_dir: &Path, locale: &str) -> Result<DisplayNames, IanaError> {
+        let supplemental = cldr_dir.join("supplemental").join("supplementalData.xml");
+        let parents = if supplemental.is_file() {
+            let text = fs::read_to_string(&supplemental)
+                    .map_err(|source| IanaError::Io { path: supplemental.clone(), source })?;
+            parse_parent_locales(&text, &supplemental)?
+        } else {
+            BTreeMap::new()
+        };
+
+        let main_dir = cldr_dir.join("main");
+        let mut result = DisplayNames::default();
+        for name in parent_chain(locale, &parents) {
+            let path = main_dir.join(format!("{}.xml", name));
+            if !path.is_file() {
+                continue;
+            }
+            let text = fs::read_to_string(&path).map_err(|source| IanaError::Io { path: path.clone(), source })?;
+            result.add_xml(&text, &path)?;
+            result.locales.push(name);
+        }
+        // root alone has (almost) no names, that's not what was asked for.
+        if result.locales.iter().all(|name| name == "root") {
+            return Err(IanaError::InvalidData {
+                path: main_dir.join(format!("{}.xml", locale.replace('-', "_"))),
+                message: format!("no CLDR locale data for \"{}\"", locale),
+            });
+        }
+        Ok(result)
+    }
+
+    /// Adds the names from one CLDR `ldml` file, without replacing the ones already known.
+    pub fn add_xml(&mut self, text: &str, path: &Path) -> Result<(), IanaError> {
+        let document = roxmltree::Document::parse(text)
+                .map_err(|err| IanaError::InvalidData { path: path.to_path_buf(), message: err.to_string() })?;
+        let Some(names) = document.descendants().find(|node| node.has_tag_name("localeDisplayNames")) else {
+            return Ok(());
+        };
+        for node in names.children() {
+            match node.tag_name().name() {
+                "languages" => fill(&mut self.languages, node, "language"),
+                "scripts" => fill(&mut self.scripts, node, "script"),
+                "territories" => fill(&mut self.territories, node, "territory"),
+                "variants" => fill(&mut self.variants, node, "variant"),
+                "localeDisplayPattern" => {
+                    for child in node.children() {
+                        let text = child.text().map(str::to_string);
+                        match child.tag_name().name() {
+                            "localePattern" if self.pattern.is_none() => self.pattern = text,
+                            "localeSeparator" if self.separator.is_none() => self.separator = text,
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    pub fn language(&self, code: &str) -> Option<&str> {
+        self.languages.get(&code.to_lowercase()).map(String::as_str)
+    }
+
+    pub fn script(&self, code: &str) -> Option<&str> {
+        self.scripts.get(&code.to_lowercase()).map(String::as_str)
+    }
+
+    pub fn territory(&self, code: &str) -> Option<&str> {
+        self.territories.get(&code.to_lowercase()).map(String::as_str)
+    }
+
+    pub fn variant(&self, code: &str) -> Option<&str> {
+        self.variants.get(&code.to_lowercase()).map(String::as_str)
+    }
+
+    /// The display name of a tag: `de-CH` => `allemand (Suisse)` in French.
+    /// Subtags without a name are shown as they are, extensions are ignored.
+    pub fn tag_name(&self, tag: &LanguageTag) -> String {
+        let language = self.language(&tag.language).unwrap_or(&tag.language).to_string();
+        let mut qualifiers: Vec<&str> = Vec::new();
+        if let Some(script) = &tag.script {
+            qualifiers.push(self.script(script).unwrap_or(script));
+        }
+        if let Some(region) = &tag.region {
+            qualifiers.push(self.territory(region).unwrap_or(region));
+        }
+        for variant in &tag.variants {
+            qualifiers.push(self.variant(variant).unwrap_or(variant));
+        }
+        let Some((first, rest)) = qualifiers.split_first() else {
+            return language;
+        };
+        let separator = self.separator.as_deref().unwrap_or("{0}, {1}");
+        let joined = rest.iter().fold(first.to_string(), |all, next| format_pattern(separator, &all, next));
+        format_pattern(self.pattern.as_deref().unwrap_or("{0} ({1})"), &language, &joined)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FR: &str = r#"<ldml><localeDisplayNames>
+        <localeDisplayPattern>
+            <localePattern>{0} ({1})</localePattern>
+            <localeSeparator>{0}, {1}</localeSeparator>
+        </localeDisplayPattern>
+        <languages>
+            <language type="de">allemand</language>
+            <language type="de_CH">allemand suisse</language>
+            <language type="en">anglais</language>
+        </languages>
+        <scripts><script type="Latn">latin</script></scripts>
+        <territories>
+            <territory type="CH">Suisse</territory>
+            <territory type="US">États-Unis</territory>
+            <territory type="US" alt="short">É.-U.</territory>
+        </territories>
+    </localeDisplayNames></ldml>"#;
+    const FR_CA: &str = r#"<ldml><localeDisplayNames>
+        <territories><territory type="US">É.-U.</territory></territories>
+    </localeDisplayNames></ldml>"#;
+
+    #[test]
+    fn test_tag_name() {
+        let mut names = DisplayNames::default();
+        names.add_xml(FR_CA, Path::new("fr_CA.xml")).unwrap();
+        names.add_xml(FR, Path::new("fr.xml")).unwrap();
+        let tag = |text: &str| LanguageTag::parse(text).unwrap();
+        assert_eq!(names.tag_name(&tag("de-CH")), "allemand (Suisse)");
+        assert_eq!(names.tag_name(&tag("en")), "anglais");
+        assert_eq!(names.tag_name(&tag("en-Latn-US")), "anglais (latin, É.-U.)");
+        assert_eq!(names.tag_name(&tag("xx-CH")), "xx (Suisse)");
+    }
+
+    #[test]
+    fn test_parent_chain() {
+        let supplemental = r#"<supplementalData><parentLocales>
+            <parentLocale parent="es_419" locales="es_AR es_MX"/>
+        </parentLocales>
+        <parentLocales component="segmentations">
+            <parentLocale parent="en" locales="fr_CA"/>
+        </parentLocales></supplementalData>"#;
+        let parents = parse_parent_locales(supplemental, Path::new("supplementalData.xml")).unwrap();
+        assert_eq!(parent_chain("es-AR", &parents), vec!["es_AR", "es_419", "es", "root"]);
+        assert_eq!(parent_chain("fr-CA", &parents), vec!["fr_CA", "fr", "root"]);
+        assert_eq!(parent_chain("zh-Hant-TW", &parents), vec!["zh_Hant_TW", "zh_Hant", "zh", "root"]);
+    }
+}
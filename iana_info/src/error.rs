@@ -0,0 +1,40 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// Everything that can go wrong in `iana_info`, other than "nothing matched".
+#[derive(Debug)]
+pub enum IanaError {
+    /// The data file could not be opened or read.
+    Io { path: PathBuf, source: io::Error },
+    /// A line in a data file that doesn't follow the expected format.
+    Malformed { path: PathBuf, line_number: usize, line: String, reason: &'static str },
+    /// A data file that can't be used, for reasons other than a bad line (e.g. invalid XML).
+    InvalidData { path: PathBuf, message: String },
+    /// Bad command line arguments.
+    Usage(String),
+    /// Not really an error, `-h` / `--help` was requested.
+    HelpRequested,
+}
+
+impl fmt::Display for IanaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IanaError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            IanaError::Malformed { path, line_number, line, reason } =>
+                write!(f, "{}:{}: {}: {:?}", path.display(), line_number, reason, line),
+            IanaError::InvalidData { path, message } => write!(f, "{}: {}", path.display(), message),
+            IanaError::Usage(message) => write!(f, "{}", message),
+            IanaError::HelpRequested => write!(f, "help requested"),
+        }
+    }
+}
+
+impl std::error::Error for IanaError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IanaError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
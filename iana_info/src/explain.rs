@@ -0,0 +1,116 @@
+use crate::bcp47::Bcp47Data;
+use crate::extension::{TransformExtension, UnicodeExtension};
+use crate::registry::Registry;
+use crate::tag::LanguageTag;
+
+/// One part of a tag (a subtag, or a multi-subtag extension value), and what it means.
+#[derive(Debug, PartialEq)]
+pub struct Part {
+    pub text: String,
+    /// `language`, `script`, `region`, `u key`, `t value`, ...
+    pub kind: String,
+    /// Empty if unknown.
+    pub description: String,
+}
+
+fn part(text: &str, kind: &str, description: &str) -> Part {
+    Part { text: text.to_string(), kind: kind.to_string(), description: description.to_string() }
+}
+
+fn registry_part(registry: &Registry, kind: &str, subtag: &str) -> Part {
+    let description = registry.find(kind, subtag).map_or("", |record| record.first_description());
+    part(subtag, kind, description)
+}
+
+fn language_parts(registry: &Registry, tag: &LanguageTag, result: &mut Vec<Part>) {
+    if !tag.language.is_empty() {
+        result.push(registry_part(registry, "language", &tag.language));
+    }
+    for extlang in &tag.extlangs {
+        result.push(registry_part(registry, "extlang", extlang));
+    }
+    if let Some(script) = &tag.script {
+        result.push(registry_part(registry, "script", script));
+    }
+    if let Some(region) = &tag.region {
+        result.push(registry_part(registry, "region", region));
+    }
+    for variant in &tag.variants {
+        result.push(registry_part(registry, "variant", variant));
+    }
+}
+
+fn keyword_parts(bcp47: Option<&Bcp47Data>, extension: char, keywords: &[(String, String)], result: &mut Vec<Part>) {
+    for (name, value) in keywords {
+        let key = bcp47.and_then(|data| data.key(extension, name));
+        result.push(part(name, &format!("{} key", extension), key.map_or("", |key| &key.description)));
+        let found = key.and_then(|key| key.find_type(value));
+        result.push(part(value, &format!("{} value", extension), found.map_or("", |found| &found.description)));
+    }
+}
+
+/// Splits a tag into its parts, with the registry descriptions of the subtags,
+/// and the CLDR descriptions of the `-u-` and `-t-` keys and values (if available).
+pub fn explain(registry: &Registry, bcp47: Option<&Bcp47Data>, tag: &str) -> Result<Vec<Part>, String> {
+    // Grandfathered tags don't follow the syntax, they can only be explained as a whole.
+    if let Some(record) = registry.find("grandfathered", tag) {
+        return Ok(vec![part(tag, "grandfathered", record.first_description())]);
+    }
+
+    let parsed = LanguageTag::parse(tag)?;
+    let mut result: Vec<Part> = Vec::new();
+    language_parts(registry, &parsed, &mut result);
+    for (singleton, subtags) in &parsed.extensions {
+        match singleton {
+            'u' => {
+                result.push(part("u", "extension", "Unicode locale extension"));
+                let ext = UnicodeExtension::parse(subtags)?;
+                for attribute in &ext.attributes {
+                    result.push(part(attribute, "u attribute", ""));
+                }
+                keyword_parts(bcp47, 'u', &ext.keywords, &mut result);
+            }
+            't' => {
+                result.push(part("t", "extension", "Transformed content"));
+                let ext = TransformExtension::parse(subtags)?;
+                if let Some(tlang) = &ext.tlang {
+                    language_parts(registry, tlang, &mut result);
+                }
+                keyword_parts(bcp47, 't', &ext.fields, &mut result);
+            }
+            _ => {
+                result.push(part(&singleton.to_string(), "extension", ""));
+                for subtag in subtags {
+                    result.push(part(subtag, "extension value", ""));
+                }
+            }
+        }
+    }
+    if !parsed.private_use.is_empty() {
+        result.push(part("x", "private use", "Private use"));
+        for subtag in &parsed.private_use {
+            result.push(part(subtag, "private use", ""));
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_explain_private_use() {
+        let sample = "%%\nType: language\nSubtag: qaa..qtz\nDescription: Private use\n\
+                %%\nType: script\nSubtag: Qaaa..Qabx\nDescription: Private use\n\
+                %%\nType: region\nSubtag: XA..XZ\nDescription: Private use\n";
+        let registry = Registry::parse(sample.as_bytes(), Path::new("sample")).unwrap();
+        let parts = explain(&registry, None, "qaa-Qaab-XA").unwrap();
+        assert_eq!(parts, vec![
+            part("qaa", "language", "Private use"),
+            part("Qaab", "script", "Private use"),
+            part("XA", "region", "Private use"),
+        ]);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::tag::LanguageTag;
+
+/// A Unicode locale extension (`-u-`, RFC 6067 / UTS #35).
+///
+/// For `de-u-co-phonebk-ka-shifted` the keywords are `co` = `phonebk`
+/// and `ka` = `shifted`. A key without a type has the type `true`.
+#[derive(Debug, Default, PartialEq)]
+pub struct UnicodeExtension {
+    pub attributes: Vec<String>,
+    pub keywords: Vec<(String, String)>,
+}
+
+/// A transformed content extension (`-t-`, RFC 6497).
+///
+/// For `ja-t-it-m0-ungegn` the source language (`tlang`) is `it`,
+/// and the fields are `m0` = `ungegn`.
+#[derive(Debug, Default, PartialEq)]
+pub struct TransformExtension {
+    pub tlang: Option<LanguageTag>,
+    pub fields: Vec<(String, String)>,
+}
+
+fn is_alphanum(subtag: &str, min: usize, max: usize) -> bool {
+    (min..=max).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+// u keys are alphanum + alpha (`ca`, `kb`, `d0` is NOT a u key)
+fn is_unicode_key(subtag: &str) -> bool {
+    let bytes = subtag.as_bytes();
+    bytes.len() == 2 && bytes[0].is_ascii_alphanumeric() && bytes[1].is_ascii_alphabetic()
+}
+
+// t field separators are alpha + digit (`m0`, `h0`, `x0`)
+fn is_transform_key(subtag: &str) -> bool {
+    let bytes = subtag.as_bytes();
+    bytes.len() == 2 && bytes[0].is_ascii_alphabetic() && bytes[1].is_ascii_digit()
+}
+
+// Groups "key type type key type ..." into (key, "type-type") pairs.
+fn parse_keywords(subtags: &[String], is_key: fn(&str) -> bool, default_type: &str)
+        -> Result<Vec<(String, String)>, String> {
+    let mut result: Vec<(String, String)> = Vec::new();
+    for subtag in subtags {
+        let subtag = subtag.to_ascii_lowercase();
+        if is_key(&subtag) {
+            if result.iter().any(|(key, _)| *key == subtag) {
+                return Err(format!("duplicate key \"{}\"", subtag));
+            }
+            result.push((subtag, String::new()));
+        } else if is_alphanum(&subtag, 3, 8) {
+            match result.last_mut() {
+                Some((_, value)) if value.is_empty() => *value = subtag,
+                Some((_, value)) => *value = format!("{}-{}", value, subtag),
+                None => return Err(format!("value \"{}\" without a key", subtag)),
+            }
+        } else {
+            return Err(format!("invalid subtag \"{}\"", subtag));
+        }
+    }
+    for (_, value) in result.iter_mut() {
+        if value.is_empty() {
+            if default_type.is_empty() {
+                return Err("key without a value".to_string());
+            }
+            *value = default_type.to_string();
+        }
+    }
+    Ok(result)
+}
+
+impl UnicodeExtension {
+    /// Parses the subtags following the `u` singleton.
+    pub fn parse(subtags: &[String]) -> Result<UnicodeExtension, String> {
+        let mut result = UnicodeExtension::default();
+        let first_key = subtags.iter().position(|s| is_unicode_key(s)).unwrap_or(subtags.len());
+        for attribute in &subtags[..first_key] {
+            if !is_alphanum(attribute, 3, 8) {
+                return Err(format!("invalid attribute \"{}\"", attribute));
+            }
+            result.attributes.push(attribute.to_ascii_lowercase());
+        }
+        result.keywords = parse_keywords(&subtags[first_key..], is_unicode_key, "true")?;
+        Ok(result)
+    }
+}
+
+impl TransformExtension {
+    /// Parses the subtags following the `t` singleton.
+    pub fn parse(subtags: &[String]) -> Result<TransformExtension, String> {
+        let mut result = TransformExtension::default();
+        let first_key = subtags.iter().position(|s| is_transform_key(s)).unwrap_or(subtags.len());
+        if first_key > 0 {
+            let tlang = LanguageTag::parse(&subtags[..first_key].join("-"))
+                    .map_err(|message| format!("invalid source language: {}", message))?;
+            if !tlang.extensions.is_empty() || !tlang.private_use.is_empty() || tlang.language.is_empty() {
+                return Err(format!("invalid source language \"{}\"", tlang));
+            }
+            result.tlang = Some(tlang);
+        }
+        result.fields = parse_keywords(&subtags[first_key..], is_transform_key, "")?;
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subtags(text: &str) -> Vec<String> {
+        text.split('-').map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_unicode_extension() {
+        let ext = UnicodeExtension::parse(&subtags("co-phonebk-ca-islamic-civil-kb")).unwrap();
+        assert!(ext.attributes.is_empty());
+        assert_eq!(ext.keywords, vec![
+            ("co".to_string(), "phonebk".to_string()),
+            ("ca".to_string(), "islamic-civil".to_string()),
+            ("kb".to_string(), "true".to_string()),
+        ]);
+        let ext = UnicodeExtension::parse(&subtags("attr-nu-arab")).unwrap();
+        assert_eq!(ext.attributes, vec!["attr"]);
+        assert!(UnicodeExtension::parse(&subtags("ca-gregory-ca-buddhist")).is_err());
+    }
+
+    #[test]
+    fn test_transform_extension() {
+        let ext = TransformExtension::parse(&subtags("it-m0-ungegn")).unwrap();
+        assert_eq!(ext.tlang.unwrap().language, "it");
+        assert_eq!(ext.fields, vec![("m0".to_string(), "ungegn".to_string())]);
+        let ext = TransformExtension::parse(&subtags("und-Cyrl-m0-bgn-1983")).unwrap();
+        assert_eq!(ext.tlang.unwrap().script.as_deref(), Some("Cyrl"));
+        assert_eq!(ext.fields, vec![("m0".to_string(), "bgn-1983".to_string())]);
+        assert!(TransformExtension::parse(&subtags("m0")).is_err());
+    }
+}
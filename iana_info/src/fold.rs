@@ -0,0 +1,58 @@
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+// Letters that carry a diacritic but have no canonical decomposition,
+// so NFD alone does not reduce them to their base letter.
+fn fold_special(ch: char) -> Option<&'static str> {
+    match ch {
+        'ß' | 'ẞ' => Some("ss"),
+        'æ' | 'Æ' => Some("ae"),
+        'œ' | 'Œ' => Some("oe"),
+        'ø' | 'Ø' => Some("o"),
+        'đ' | 'Đ' => Some("d"),
+        'ð' | 'Ð' => Some("d"),
+        'ħ' | 'Ħ' => Some("h"),
+        'ı' => Some("i"),
+        'ł' | 'Ł' => Some("l"),
+        'ŧ' | 'Ŧ' => Some("t"),
+        'þ' | 'Þ' => Some("th"),
+        'ς' => Some("σ"),
+        _ => None,
+    }
+}
+
+/// Folds a string for case- and accent-insensitive comparison.
+///
+/// The text is decomposed (NFD), combining marks are dropped, and the rest
+/// is lowercased, so "Provençal", "PROVENCAL" and "provencal" all fold to
+/// the same value.
+pub fn fold(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for ch in text.nfd() {
+        if is_combining_mark(ch) {
+            continue;
+        }
+        match fold_special(ch) {
+            Some(replacement) => result.push_str(replacement),
+            None => result.extend(ch.to_lowercase()),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold() {
+        assert_eq!(fold("Provençal"), "provencal");
+        assert_eq!(fold("PROVENÇAL"), "provencal");
+        assert_eq!(fold("Norwegian Bokmål"), "norwegian bokmal");
+        assert_eq!(fold("Volapük"), "volapuk");
+        assert_eq!(fold("Færøsk"), "faerosk");
+        assert_eq!(fold("Straße"), "strasse");
+        assert_eq!(fold("Łódź"), "lodz");
+        assert_eq!(fold("nynorsk"), "nynorsk");
+    }
+}
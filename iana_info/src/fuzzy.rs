@@ -0,0 +1,59 @@
+use crate::fold;
+
+/// Classic Levenshtein edit distance, counted in chars (not bytes).
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr: Vec<usize> = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// How far a query is from a field value, ignoring case and accents.
+///
+/// Besides the whole value, each word of the value is also tried (with a
+/// penalty of one edit), so "provencal" is close to "Old Provençal (to 1500)".
+pub fn distance(query: &str, value: &str) -> usize {
+    let query = fold::fold(query.trim_start_matches('='));
+    let value = fold::fold(value);
+    let mut best = levenshtein(&query, &value);
+    for word in value.split(|c: char| !c.is_alphanumeric()) {
+        if !word.is_empty() {
+            best = best.min(levenshtein(&query, word) + 1);
+        }
+    }
+    best
+}
+
+/// The largest distance still worth proposing as a "did you mean" suggestion.
+pub fn max_suggestion_distance(query: &str) -> usize {
+    (query.trim_start_matches('=').chars().count() / 3).max(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("eng", "en"), 1);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("portugese", "portuguese"), 1);
+    }
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance("=EN", "en"), 0);
+        assert_eq!(distance("portugese", "Portuguese"), 1);
+        assert_eq!(distance("provencal", "Old Provençal (to 1500)"), 1);
+    }
+}
@@ -0,0 +1,247 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use crate::error::IanaError;
+use crate::record::Record;
+use crate::registry::Registry;
+
+/// The ISO 639-3 code table, from SIL (`Id Part2B Part2T Part1 Scope Language_Type Ref_Name Comment`).
+pub const ISO_639_3_FILE_NAME:&str = "iso-639-3.tab";
+/// The ISO 639-3 macrolanguage mappings, from SIL (`M_Id I_Id I_Status`).
+pub const ISO_639_3_MACRO_FILE_NAME:&str = "iso-639-3-macrolanguages.tab";
+/// The ISO 639-2 code list, from the Library of Congress (`B|T|639-1|English|French`).
+/// Only needed for the 639-2 codes that are not in 639-3 (collections, like `afa` or `sla`).
+pub const ISO_639_2_FILE_NAME:&str = "ISO-639-2_utf-8.txt";
+
+/// One language, with its codes in all the ISO 639 parts (empty if it has no code in a part).
+#[derive(Debug, Default, PartialEq)]
+pub struct Iso639Code {
+    pub part3: String,
+    /// The bibliographic code (`fre`, `ger`), only set if different from the terminology code.
+    pub part2b: String,
+    pub part2t: String,
+    pub part1: String,
+    /// `I`ndividual, `M`acrolanguage, `S`pecial, or empty (639-2 collections).
+    pub scope: String,
+    /// `A`ncient, `C`onstructed, `E`xtinct, `H`istorical, `L`iving, `S`pecial.
+    pub language_type: String,
+    pub name: String,
+    /// The 639-3 code of the macrolanguage this language belongs to.
+    pub macrolanguage: Option<String>,
+}
+
+/// The ISO 639 codes, as loaded from the optional tables in `udata`.
+#[derive(Debug, Default)]
+pub struct Iso639Table {
+    pub codes: Vec<Iso639Code>,
+}
+
+/// An ISO 639 code translated to BCP 47.
+#[derive(Debug)]
+pub struct Crosswalk<'a> {
+    pub code: &'a Iso639Code,
+    /// The subtag BCP 47 uses for the language: the 639-1 code if there is one, else the 3 letter code.
+    pub subtag: String,
+    /// The registry record of the subtag (`None` if the subtag is not registered).
+    pub record: Option<&'a Record>,
+    /// The BCP 47 subtag of the macrolanguage (`zh` for `cmn`), like the `Macrolanguage` field of the registry.
+    pub macrolanguage: Option<String>,
+}
+
+fn open(path: &Path) -> Result<BufReader<File>, IanaError> {
+    let file = File::open(path).map_err(|source| IanaError::Io { path: path.to_path_buf(), source })?;
+    Ok(BufReader::new(file))
+}
+
+// The data lines of a table (no header, no BOM, no trailing whitespace), with their line numbers.
+fn data_lines<R: BufRead>(reader: R, path: &Path, has_header: bool) -> Result<Vec<(usize, String)>, IanaError> {
+    let mut result: Vec<(usize, String)> = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(|source| IanaError::Io { path: path.to_path_buf(), source })?;
+        let line = line.trim_start_matches('\u{feff}').trim_end();
+        if (has_header && index == 0) || line.is_empty() {
+            continue;
+        }
+        result.push((index + 1, line.to_string()));
+    }
+    Ok(result)
+}
+
+fn malformed(path: &Path, line_number: usize, line: &str, reason: &'static str) -> IanaError {
+    IanaError::Malformed { path: path.to_path_buf(), line_number, line: line.to_string(), reason }
+}
+
+impl Iso639Code {
+    /// All the codes of the language, in all the parts.
+    pub fn all_codes(&self) -> impl Iterator<Item = &str> {
+        [&self.part3, &self.part2b, &self.part2t, &self.part1].into_iter()
+            .map(String::as_str)
+            .filter(|code| !code.is_empty())
+    }
+
+    /// The subtag BCP 47 uses for this language (RFC 5646, section 2.2.1).
+    pub fn bcp47_subtag(&self) -> &str {
+        [&self.part1, &self.part3, &self.part2t, &self.part2b].into_iter()
+            .find(|code| !code.is_empty())
+            .map_or("", String::as_str)
+    }
+}
+
+impl Iso639Table {
+    /// Loads the tables found in the folder, all of them are optional.
+    /// The result is empty if there are none.
+    pub fn load_dir(dir: &Path) -> Result<Iso639Table, IanaError> {
+        let mut result = Iso639Table::default();
+        let path = dir.join(ISO_639_3_FILE_NAME);
+        if path.is_file() {
+            result.add_639_3(open(&path)?, &path)?;
+        }
+        let path = dir.join(ISO_639_3_MACRO_FILE_NAME);
+        if path.is_file() {
+            result.add_macrolanguages(open(&path)?, &path)?;
+        }
+        let path = dir.join(ISO_639_2_FILE_NAME);
+        if path.is_file() {
+            result.add_639_2(open(&path)?, &path)?;
+        }
+        Ok(result)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    /// Adds the codes from a `iso-639-3.tab` file.
+    pub fn add_639_3<R: BufRead>(&mut self, reader: R, path: &Path) -> Result<(), IanaError> {
+        for (line_number, line) in data_lines(reader, path, true)? {
+            let columns: Vec<&str> = line.split('\t').collect();
+            if columns.len() < 7 {
+                return Err(malformed(path, line_number, &line, "expected at least 7 tab separated columns"));
+            }
+            let mut code = Iso639Code {
+                part3: columns[0].to_string(),
+                part2b: columns[1].to_string(),
+                part2t: columns[2].to_string(),
+                part1: columns[3].to_string(),
+                scope: columns[4].to_string(),
+                language_type: columns[5].to_string(),
+                name: columns[6].to_string(),
+                macrolanguage: None,
+            };
+            if code.part2b == code.part2t {
+                code.part2b.clear();
+            }
+            self.codes.push(code);
+        }
+        Ok(())
+    }
+
+    /// Adds the macrolanguages from a `iso-639-3-macrolanguages.tab` file.
+    /// Retired mappings are ignored.
+    pub fn add_macrolanguages<R: BufRead>(&mut self, reader: R, path: &Path) -> Result<(), IanaError> {
+        for (line_number, line) in data_lines(reader, path, true)? {
+            let columns: Vec<&str> = line.split('\t').collect();
+            if columns.len() < 2 {
+                return Err(malformed(path, line_number, &line, "expected at least 2 tab separated columns"));
+            }
+            if columns.get(2).is_some_and(|status| *status == "R") {
+                continue;
+            }
+            if let Some(code) = self.codes.iter_mut().find(|code| code.part3 == columns[1]) {
+                code.macrolanguage = Some(columns[0].to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds the codes from a `ISO-639-2_utf-8.txt` file that are not already known from 639-3.
+    pub fn add_639_2<R: BufRead>(&mut self, reader: R, path: &Path) -> Result<(), IanaError> {
+        for (line_number, line) in data_lines(reader, path, false)? {
+            let columns: Vec<&str> = line.split('|').collect();
+            if columns.len() < 4 {
+                return Err(malformed(path, line_number, &line, "expected at least 4 | separated columns"));
+            }
+            // The qaa-qtz range reserved for local use.
+            if columns[0].contains('-') {
+                continue;
+            }
+            let (part2b, part2t) = if columns[1].is_empty() { ("", columns[0]) } else { (columns[0], columns[1]) };
+            if let Some(known) = self.codes.iter_mut().find(|code| code.part2t == part2t) {
+                if known.part1.is_empty() {
+                    known.part1 = columns[2].to_string();
+                }
+                continue;
+            }
+            self.codes.push(Iso639Code {
+                part2b: part2b.to_string(),
+                part2t: part2t.to_string(),
+                part1: columns[2].to_string(),
+                name: columns[3].to_string(),
+                ..Default::default()
+            });
+        }
+        Ok(())
+    }
+
+    /// Finds the language with the given 639-1, 639-2/B, 639-2/T or 639-3 code, ignoring case.
+    pub fn find(&self, code: &str) -> Option<&Iso639Code> {
+        self.codes.iter().find(|known| known.all_codes().any(|c| c.eq_ignore_ascii_case(code)))
+    }
+
+    /// Translates any ISO 639 code (or a BCP 47 language subtag) to the BCP 47 subtag,
+    /// with all the other ISO 639 codes of the language.
+    pub fn crosswalk<'a>(&'a self, registry: &'a Registry, code: &str) -> Option<Crosswalk<'a>> {
+        let found = self.find(code)?;
+        let subtag = found.bcp47_subtag().to_string();
+        let record = registry.find("language", &subtag);
+        let macrolanguage = found.macrolanguage.as_ref().map(|part3| {
+            self.find(part3).map_or(part3.as_str(), Iso639Code::bcp47_subtag).to_string()
+        });
+        Some(Crosswalk { code: found, subtag, record, macrolanguage })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_639_3: &str = "Id\tPart2B\tPart2T\tPart1\tScope\tLanguage_Type\tRef_Name\tComment
+fra\tfre\tfra\tfr\tI\tL\tFrench\t
+deu\tger\tdeu\tde\tI\tL\tGerman\t
+cmn\t\t\t\tI\tL\tMandarin Chinese\t
+zho\tchi\tzho\tzh\tM\tL\tChinese\t
+eng\teng\teng\ten\tI\tL\tEnglish\t
+";
+    const SAMPLE_MACRO: &str = "M_Id\tI_Id\tI_Status\nzho\tcmn\tA\nzho\txyz\tR\n";
+    const SAMPLE_639_2: &str = "\u{feff}fre|fra|fr|French|français\nsla|||Slavic languages|slaves, langues\nqaa-qtz|||Reserved for local use|réservée à l'usage local\n";
+
+    #[test]
+    fn test_crosswalk() {
+        let mut table = Iso639Table::default();
+        let path = Path::new("sample");
+        table.add_639_3(SAMPLE_639_3.as_bytes(), path).unwrap();
+        table.add_macrolanguages(SAMPLE_MACRO.as_bytes(), path).unwrap();
+        table.add_639_2(SAMPLE_639_2.as_bytes(), path).unwrap();
+        assert_eq!(table.codes.len(), 6);
+
+        assert_eq!(table.find("FRE").unwrap().bcp47_subtag(), "fr");
+        assert_eq!(table.find("ger").unwrap().part3, "deu");
+        assert_eq!(table.find("de").unwrap().part2b, "ger");
+        assert_eq!(table.find("eng").unwrap().part2b, "");
+        let mandarin = table.find("cmn").unwrap();
+        assert_eq!(mandarin.bcp47_subtag(), "cmn");
+        assert_eq!(mandarin.macrolanguage.as_deref(), Some("zho"));
+        assert_eq!(table.find("chi").unwrap().bcp47_subtag(), "zh");
+        assert_eq!(table.find("sla").unwrap().bcp47_subtag(), "sla");
+        assert!(table.find("qaa").is_none());
+
+        let registry = Registry::parse("%%\nType: language\nSubtag: fr\nDescription: French\n".as_bytes(), path).unwrap();
+        let crosswalk = table.crosswalk(&registry, "fre").unwrap();
+        assert_eq!(crosswalk.subtag, "fr");
+        assert_eq!(crosswalk.record.unwrap().first_description(), "French");
+        assert!(table.crosswalk(&registry, "ger").unwrap().record.is_none());
+        assert_eq!(table.crosswalk(&registry, "cmn").unwrap().macrolanguage.as_deref(), Some("zh"));
+        assert!(table.crosswalk(&registry, "fra").unwrap().macrolanguage.is_none());
+    }
+}
@@ -0,0 +1,42 @@
+//! Access to the IANA Language Subtag Registry: loading the registry file,
+//! looking up and searching records, and parsing and checking language tags
+//! (including the `-u-` and `-t-` extensions, with the CLDR BCP 47 data),
+//! and translating ISO 639 codes to BCP 47 subtags.
+//!
+//! ```no_run
+//! use iana_info::{Query, Registry};
+//!
+//! let registry = Registry::load(&iana_info::registry::default_data_path()).unwrap();
+//! let mut query = Query::new();
+//! query.add("Type", "=language");
+//! query.add("Description", "provencal");
+//! query.fold = true;
+//! for record in registry.search(&query) {
+//!     println!("{}: {}", record.id(), record.first_description());
+//! }
+//! ```
+
+pub mod bcp47;
+pub mod compose;
+pub mod display;
+pub mod error;
+pub mod explain;
+pub mod extension;
+pub mod fold;
+pub mod fuzzy;
+pub mod iso639;
+pub mod likely;
+pub mod lint;
+pub mod record;
+pub mod registry;
+pub mod related;
+pub mod search;
+pub mod tag;
+
+pub use bcp47::Bcp47Data;
+pub use error::IanaError;
+pub use iso639::Iso639Table;
+pub use record::Record;
+pub use registry::Registry;
+pub use search::{Query, SortKey, Suggestion};
+pub use tag::LanguageTag;
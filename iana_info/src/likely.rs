@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::error::IanaError;
+use crate::tag::LanguageTag;
+
+/// The CLDR likely subtags file, in the `supplemental` folder of the CLDR data.
+pub const LIKELY_SUBTAGS_FILE_NAME:&str = "likelySubtags.xml";
+
+/// The CLDR likely subtags (`zh_TW` => `zh_Hant_TW`), for the "Add Likely Subtags"
+/// and "Remove Likely Subtags" algorithms of UTS #35.
+#[derive(Debug, Default)]
+pub struct LikelySubtags {
+    // Keyed by the lowercase `from` (`zh_tw`), the values are (language, script, region).
+    map: BTreeMap<String, (String, String, String)>,
+}
+
+fn key(language: &str, script: Option<&str>, region: Option<&str>) -> String {
+    let mut result = language.to_lowercase();
+    for part in [script, region].into_iter().flatten() {
+        result.push('_');
+        result.push_str(&part.to_lowercase());
+    }
+    result
+}
+
+fn title_case(text: &str) -> String {
+    let lower = text.to_lowercase();
+    let mut chars = lower.chars();
+    chars.next().map_or(String::new(), |first| first.to_uppercase().chain(chars).collect())
+}
+
+// Language, script and region in canonical case, with an extlang replacing its prefix (zh-yue => yue).
+fn normalize(tag: &LanguageTag) -> LanguageTag {
+    let language = tag.extlangs.first().unwrap_or(&tag.language);
+    LanguageTag {
+        language: if language.is_empty() { "und".to_string() } else { language.to_lowercase() },
+        extlangs: Vec::new(),
+        script: tag.script.as_deref().map(title_case),
+        region: tag.region.as_deref().map(str::to_uppercase),
+        ..tag.clone()
+    }
+}
+
+impl LikelySubtags {
+    pub fn load(path: &Path) -> Result<LikelySubtags, IanaError> {
+        let text = fs::read_to_string(path).map_err(|source| IanaError::Io { path: path.to_path_buf(), source })?;
+        LikelySubtags::parse(&text, path)
+    }
+
+    /// Parses the `<likelySubtag from="..." to="..."/>` elements of a CLDR `likelySubtags.xml` file.
+    pub fn parse(text: &str, path: &Path) -> Result<LikelySubtags, IanaError> {
+        let document = roxmltree::Document::parse(text)
+                .map_err(|err| IanaError::InvalidData { path: path.to_path_buf(), message: err.to_string() })?;
+        let mut result = LikelySubtags::default();
+        for node in document.descendants().filter(|node| node.has_tag_name("likelySubtag")) {
+            let (Some(from), Some(to)) = (node.attribute("from"), node.attribute("to")) else {
+                continue;
+            };
+            let parts: Vec<&str> = to.split('_').collect();
+            let [language, script, region] = parts[..] else {
+                return Err(IanaError::InvalidData {
+                    path: path.to_path_buf(),
+                    message: format!("expected language_script_region, got \"{}\"", to),
+                });
+            };
+            result.map.insert(from.to_lowercase(), (language.to_string(), script.to_string(), region.to_string()));
+        }
+        Ok(result)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Adds the likely script and region (and language, for `und`): `zh-TW` => `zh-Hant-TW`.
+    /// Variants and extensions are kept. Returns `None` if nothing is known about the tag.
+    pub fn maximize(&self, tag: &LanguageTag) -> Option<LanguageTag> {
+        if tag.language.is_empty() && !tag.private_use.is_empty() {
+            return None;
+        }
+        let tag = normalize(tag);
+        let language = tag.language.as_str();
+        let script = tag.script.as_deref();
+        let region = tag.region.as_deref();
+        let lookups = [
+            key(language, script, region),
+            key(language, None, region),
+            key(language, script, None),
+            key(language, None, None),
+            key("und", script, None),
+        ];
+        let (likely_language, likely_script, likely_region) = lookups.iter().find_map(|lookup| self.map.get(lookup))?;
+        Some(LanguageTag {
+            language: if language == "und" { likely_language.clone() } else { tag.language.clone() },
+            script: Some(tag.script.clone().unwrap_or_else(|| likely_script.clone())),
+            region: Some(tag.region.clone().unwrap_or_else(|| likely_region.clone())),
+            ..tag
+        })
+    }
+
+    /// Removes the subtags that maximize would add back: `en-Latn-US` => `en`, `zh-Hant-TW` => `zh-TW`.
+    /// Prefers keeping the region over keeping the script (`zh-TW`, not `zh-Hant`).
+    pub fn minimize(&self, tag: &LanguageTag) -> Option<LanguageTag> {
+        let max = self.maximize(tag)?;
+        let same = |trial: &LanguageTag| self.maximize(trial).is_some_and(|trial_max|
+                trial_max.language == max.language && trial_max.script == max.script && trial_max.region == max.region);
+        let language_only = LanguageTag { script: None, region: None, ..max.clone() };
+        let trials = [
+            language_only.clone(),
+            LanguageTag { region: max.region.clone(), ..language_only.clone() },
+            LanguageTag { script: max.script.clone(), ..language_only },
+        ];
+        Some(trials.into_iter().find(same).unwrap_or(max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"<supplementalData><likelySubtags>
+        <likelySubtag from="en" to="en_Latn_US"/>
+        <likelySubtag from="sr" to="sr_Cyrl_RS"/>
+        <likelySubtag from="sr_ME" to="sr_Latn_ME"/>
+        <likelySubtag from="zh" to="zh_Hans_CN"/>
+        <likelySubtag from="zh_TW" to="zh_Hant_TW"/>
+        <likelySubtag from="zh_Hant" to="zh_Hant_TW"/>
+        <likelySubtag from="yue" to="yue_Hant_HK"/>
+        <likelySubtag from="und" to="en_Latn_US"/>
+        <likelySubtag from="und_Cyrl" to="ru_Cyrl_RU"/>
+        <likelySubtag from="und_FR" to="fr_Latn_FR"/>
+    </likelySubtags></supplementalData>"#;
+
+    fn check(likely: &LikelySubtags, tag: &str, max: &str, min: &str) {
+        let parsed = LanguageTag::parse(tag).unwrap();
+        assert_eq!(likely.maximize(&parsed).unwrap().to_string(), max, "maximize {}", tag);
+        assert_eq!(likely.minimize(&parsed).unwrap().to_string(), min, "minimize {}", tag);
+    }
+
+    #[test]
+    fn test_likely_subtags() {
+        let likely = LikelySubtags::parse(SAMPLE, Path::new("likelySubtags.xml")).unwrap();
+        check(&likely, "en", "en-Latn-US", "en");
+        check(&likely, "EN-latn-us", "en-Latn-US", "en");
+        check(&likely, "zh-TW", "zh-Hant-TW", "zh-TW");
+        check(&likely, "zh-Hant", "zh-Hant-TW", "zh-TW");
+        check(&likely, "zh-Hans-CN", "zh-Hans-CN", "zh");
+        check(&likely, "sr-ME", "sr-Latn-ME", "sr-ME");
+        check(&likely, "sr-Latn-ME-1994", "sr-Latn-ME-1994", "sr-ME-1994");
+        check(&likely, "und-FR", "fr-Latn-FR", "fr-FR");
+        check(&likely, "und-Cyrl", "ru-Cyrl-RU", "ru-Cyrl");
+        check(&likely, "zh-yue", "yue-Hant-HK", "yue");
+        assert!(likely.maximize(&LanguageTag::parse("x-private").unwrap()).is_none());
+    }
+}
@@ -0,0 +1,239 @@
+use crate::bcp47::Bcp47Data;
+use crate::extension::{TransformExtension, UnicodeExtension};
+use crate::record::Record;
+use crate::registry::Registry;
+use crate::tag::LanguageTag;
+
+/// A best practice problem found in a tag, with the registry record that explains it.
+pub struct Warning<'a> {
+    pub message: String,
+    pub record: Option<&'a Record>,
+}
+
+/// The result of checking one tag.
+pub struct Report<'a> {
+    pub warnings: Vec<Warning<'a>>,
+    /// The tag with all the fixes applied, when it differs from the input.
+    pub suggested: Option<String>,
+}
+
+fn warn<'a>(warnings: &mut Vec<Warning<'a>>, message: String, record: Option<&'a Record>) {
+    warnings.push(Warning { message, record });
+}
+
+fn describe(record: &Record) -> String {
+    format!("{} \"{}\" ({})", record.kind(), record.id(), record.first_description())
+}
+
+// Warns about unknown and deprecated subtags, returns the registry record (if any).
+fn check_subtag<'a>(registry: &'a Registry, warnings: &mut Vec<Warning<'a>>,
+        kind: &str, subtag: &str) -> Option<&'a Record> {
+    let found = registry.find(kind, subtag);
+    match found {
+        None => warn(warnings, format!("unknown {} subtag \"{}\"", kind, subtag), None),
+        Some(rec) if rec.is_deprecated() => {
+            let message = match rec.get("Preferred-Value") {
+                Some(preferred) => format!("{} is deprecated, use \"{}\" instead", describe(rec), preferred),
+                None => format!("{} is deprecated", describe(rec)),
+            };
+            warn(warnings, message, Some(rec));
+        }
+        Some(_) => {}
+    }
+    found
+}
+
+fn preferred(found: Option<&Record>) -> Option<String> {
+    found.filter(|rec| rec.is_deprecated())
+        .and_then(|rec| rec.get("Preferred-Value"))
+        .map(|preferred| preferred.to_string())
+}
+
+// True if all the subtags of the prefix appear in the tag, in order.
+fn has_prefix(tag: &str, prefix: &str) -> bool {
+    let tag = tag.to_ascii_lowercase();
+    let mut subtags = tag.split('-');
+    prefix.to_ascii_lowercase().split('-').all(|wanted| subtags.any(|subtag| subtag == wanted))
+}
+
+/// Checks a tag against the registry and returns the best practice warnings.
+///
+/// Besides unknown and deprecated subtags, this warns about a script that is the
+/// `Suppress-Script` of the language, an extlang used instead of the primary
+/// language, a region used with a language that has no linguistic content,
+/// and variants used without one of their `Prefix` values.
+/// Returns an error if the tag is not well-formed.
+pub fn check<'a>(registry: &'a Registry, tag: &str) -> Result<Report<'a>, String> {
+    let mut warnings: Vec<Warning> = Vec::new();
+
+    if let Some(rec) = registry.find("grandfathered", tag) {
+        let suggested = rec.get("Preferred-Value").map(|preferred| preferred.to_string());
+        let message = match &suggested {
+            Some(preferred) => format!("\"{}\" is a grandfathered tag, use \"{}\" instead", tag, preferred),
+            None => format!("\"{}\" is a grandfathered tag and has no modern replacement", tag),
+        };
+        warn(&mut warnings, message, Some(rec));
+        return Ok(Report { warnings, suggested });
+    }
+    if let Some(rec) = registry.find("redundant", tag) {
+        if let Some(preferred) = rec.get("Preferred-Value") {
+            let message = format!("{} is deprecated, use \"{}\" instead", describe(rec), preferred);
+            warn(&mut warnings, message, Some(rec));
+            return Ok(Report { warnings, suggested: Some(preferred.to_string()) });
+        }
+    }
+
+    let parsed = LanguageTag::parse(tag)?;
+    let mut fixed = parsed.clone();
+    if parsed.language.is_empty() {
+        return Ok(Report { warnings, suggested: None });
+    }
+
+    let language = if parsed.language.len() == 4 {
+        warn(&mut warnings, format!("language subtag \"{}\" is reserved for future use", parsed.language), None);
+        None
+    } else {
+        check_subtag(registry, &mut warnings, "language", &parsed.language)
+    };
+    if let Some(preferred) = preferred(language) {
+        fixed.language = preferred;
+    }
+
+    for extlang in &parsed.extlangs {
+        let found = check_subtag(registry, &mut warnings, "extlang", extlang);
+        if let Some(rec) = found {
+            let prefix = rec.field("Prefix");
+            if !prefix.eq_ignore_ascii_case(&parsed.language) {
+                warn(&mut warnings, format!("{} should only be used after \"{}\", not \"{}\"",
+                        describe(rec), prefix, parsed.language), Some(rec));
+            } else if let Some(primary) = rec.get("Preferred-Value") {
+                warn(&mut warnings, format!("extlang form \"{}-{}\" used, the preferred form is the primary language \"{}\"",
+                        parsed.language, extlang, primary), Some(rec));
+                fixed.language = primary.to_string();
+                fixed.extlangs.clear();
+            }
+        }
+    }
+
+    // The Suppress-Script belongs to the language actually used (after the fixes above).
+    let main_language = registry.find("language", &fixed.language).or(language);
+    if let Some(script) = &parsed.script {
+        let found = check_subtag(registry, &mut warnings, "script", script);
+        if let Some(preferred) = preferred(found) {
+            fixed.script = Some(preferred);
+        }
+        if let Some(lang) = main_language {
+            if lang.field("Suppress-Script").eq_ignore_ascii_case(script) {
+                warn(&mut warnings, format!("script \"{}\" is the Suppress-Script of {}, it should be omitted",
+                        script, describe(lang)), Some(lang));
+                fixed.script = None;
+            }
+        }
+    }
+
+    if let Some(region) = &parsed.region {
+        let found = check_subtag(registry, &mut warnings, "region", region);
+        if let Some(preferred) = preferred(found) {
+            fixed.region = Some(preferred);
+        }
+        if let Some(lang) = main_language {
+            if lang.field("Scope") == "special" && lang.field("Subtag") != "und" {
+                warn(&mut warnings, format!("region \"{}\" makes no sense with the special {}",
+                        region, describe(lang)), Some(lang));
+            }
+        }
+    }
+
+    for variant in &parsed.variants {
+        let found = check_subtag(registry, &mut warnings, "variant", variant);
+        if let Some(rec) = found {
+            let prefixes = rec.values("Prefix");
+            if !prefixes.is_empty() && !prefixes.iter().any(|prefix| has_prefix(tag, prefix)) {
+                warn(&mut warnings, format!("{} is meant to be used with one of: {}",
+                        describe(rec), prefixes.join(", ")), Some(rec));
+            }
+        }
+        if let Some(preferred) = preferred(found) {
+            for fixed_variant in fixed.variants.iter_mut() {
+                if fixed_variant.eq_ignore_ascii_case(variant) {
+                    fixed_variant.clone_from(&preferred);
+                }
+            }
+        }
+    }
+
+    let fixed = fixed.to_string();
+    let suggested = if fixed != tag { Some(fixed) } else { None };
+    Ok(Report { warnings, suggested })
+}
+
+fn check_keywords(bcp47: &Bcp47Data, extension: char, keywords: &[(String, String)], warnings: &mut Vec<Warning>) {
+    for (name, value) in keywords {
+        let Some(key) = bcp47.key(extension, name) else {
+            warn(warnings, format!("unknown -{}- key \"{}\"", extension, name), None);
+            continue;
+        };
+        if key.deprecated {
+            let message = match &key.preferred {
+                Some(preferred) => format!("-{}- key \"{}\" is deprecated, use \"{}\" instead", extension, name, preferred),
+                None => format!("-{}- key \"{}\" is deprecated", extension, name),
+            };
+            warn(warnings, message, None);
+        }
+        match key.find_type(value) {
+            None => warn(warnings, format!("unknown value \"{}\" for the -{}- key \"{}\" ({}), see {}",
+                    value, extension, name, key.description, key.file_name), None),
+            Some(found) if found.deprecated => {
+                let message = match &found.preferred {
+                    Some(preferred) => format!("value \"{}\" of the -{}- key \"{}\" is deprecated, use \"{}\" instead",
+                            value, extension, name, preferred),
+                    None => format!("value \"{}\" of the -{}- key \"{}\" is deprecated", value, extension, name),
+                };
+                warn(warnings, message, None);
+            }
+            Some(found) if !found.is_pattern() && !found.name.eq_ignore_ascii_case(value) => {
+                warn(warnings, format!("value \"{}\" of the -{}- key \"{}\" is an alias, use \"{}\" instead",
+                        value, extension, name, found.name), None);
+            }
+            Some(_) => {}
+        }
+    }
+}
+
+/// Validates the `-u-` keys and types and the `-t-` fields of a tag against the CLDR data.
+/// Returns an error if the tag (or one of the extensions) is not well-formed.
+pub fn check_extensions<'a>(bcp47: &Bcp47Data, tag: &str) -> Result<Vec<Warning<'a>>, String> {
+    let parsed = LanguageTag::parse(tag)?;
+    let mut warnings: Vec<Warning> = Vec::new();
+    for (singleton, subtags) in &parsed.extensions {
+        match singleton {
+            'u' => check_keywords(bcp47, 'u', &UnicodeExtension::parse(subtags)?.keywords, &mut warnings),
+            't' => check_keywords(bcp47, 't', &TransformExtension::parse(subtags)?.fields, &mut warnings),
+            _ => warn(&mut warnings, format!("extension \"{}\" is not registered", singleton), None),
+        }
+    }
+    Ok(warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_prefix() {
+        assert!(has_prefix("sl-rozaj-biske", "sl-rozaj"));
+        assert!(has_prefix("sl-IT-rozaj-biske", "sl-rozaj"));
+        assert!(has_prefix("de-CH-1901", "de"));
+        assert!(!has_prefix("en-1901", "de"));
+        assert!(!has_prefix("rozaj-sl", "sl-rozaj"));
+    }
+
+    #[test]
+    fn test_check_reserved_language() {
+        let sample = "%%\nType: region\nSubtag: US\nDescription: United States\n";
+        let registry = Registry::parse(sample.as_bytes(), std::path::Path::new("sample")).unwrap();
+        let report = check(&registry, "abcd-US").unwrap();
+        let messages: Vec<&str> = report.warnings.iter().map(|warning| warning.message.as_str()).collect();
+        assert_eq!(messages, vec!["language subtag \"abcd\" is reserved for future use"]);
+    }
+}
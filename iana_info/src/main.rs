@@ -1,229 +1,623 @@
-use std::collections::HashMap;
 use std::env;
-use std::fs::File;
-use std::io::{self, BufRead, BufReader};
 use std::path::{Path, PathBuf};
-use std::process::exit;
+use std::process::ExitCode;
 use atty::Stream;
+use cli::{Action, Format, EXIT_DATA_ERROR, EXIT_MATCH, EXIT_NO_MATCH, EXIT_USAGE};
+use config::Config;
+use iana_info::likely::{self, LikelySubtags};
+use iana_info::related::Relations;
+use iana_info::display::{self, DisplayNames};
+use iana_info::{bcp47, compose, explain, lint, registry, search, Bcp47Data, IanaError, Iso639Table, LanguageTag, Query, Record, Registry, SortKey};
 
-const DEFAULT_IANA_FILE_NAME:&str = "language-subtag-registry";
-static mut USE_COLORS:bool = false;
+mod cli;
+mod completions;
+mod config;
 
-fn set_use_colors(use_col:bool) {
-    unsafe { USE_COLORS = use_col };
+const MAX_SUGGESTIONS:usize = 5;
+const MAX_FUZZY_RESULTS:usize = 10;
+
+#[derive(Debug, Default)]
+struct Options {
+    use_colors: bool,
+    quiet: bool,
+    fuzzy: bool,
+    sort: Option<SortKey>,
+    reverse: bool,
+    limit: Option<usize>,
+    format: Format,
+    related: bool,
+    /// The shorthand used (`-l fr` is ("language", "fr")), there can only be one.
+    shorthand: Option<(&'static str, String)>,
+    /// The CLDR locale for the display names shown by explain.
+    display_in: Option<String>,
+    /// Only show these fields, all of them if empty.
+    fields: Vec<String>,
+    /// The `udata` folder, with the registry and the optional data files.
+    data_dir: PathBuf,
+    query: Query,
+}
+
+fn print_field(options: &Options, indent: &str, prefix: &str, postfix: &str) {
+    let separator = if postfix.is_empty() { "" } else { " " };
+    if options.use_colors {
+        println!("{}\x1b[93m{}:\x1b[m{}{}", indent, prefix, separator, postfix);
+    } else {
+        println!("{}{}:{}{}", indent, prefix, separator, postfix);
+    }
 }
 
-fn get_use_colors() -> bool {
-    unsafe { return USE_COLORS };
+fn print_record(options: &Options, indent: &str, record: &Record) {
+    for (prefix, postfix) in record.fields() {
+        print_field(options, indent, prefix, postfix);
+    }
+}
+
+fn print_selected_fields(options: &Options, indent: &str, record: &Record) {
+    if options.fields.is_empty() {
+        print_record(options, indent, record);
+        return;
+    }
+    for (prefix, postfix) in record.fields() {
+        if options.fields.iter().any(|name| name.eq_ignore_ascii_case(prefix)) {
+            print_field(options, indent, prefix, postfix);
+        }
+    }
 }
 
-fn read_lines(data_path:&Path) -> io::Lines<BufReader<File>> {
-    let file = File::open(data_path).unwrap(); 
-    return io::BufReader::new(file).lines(); 
+fn print_related(options: &Options, relations: &Relations, record: &Record) {
+    for relation in relations.of(record) {
+        // "mo => Preferred-Value: ro", and from ro: "Preferred-Value of: mo"
+        let label = if relation.reverse { format!("{} of", relation.field) } else { relation.field.to_string() };
+        match options.format {
+            Format::Full => {
+                print_field(options, "  ", &format!("related {}", label), "");
+                print_selected_fields(options, "      ", relation.record);
+            }
+            Format::Brief => print_field(options, "  ", &label, &record_title(relation.record, true)),
+        }
+    }
 }
 
-fn vec_to_map(record: &Vec<String>) -> HashMap<String, String> {
-    let mut result:HashMap<String, String> = HashMap::new();
-    for line in record {
-        let parts = line.split_once(": ");
-        let prefix = parts.unwrap().0;
-        let suffix = parts.unwrap().1;
+fn record_title(record: &Record, with_description: bool) -> String {
+    if !with_description {
+        return format!("{} ({})", record.id(), record.kind());
+    }
+    format!("{} ({}: {})", record.id(), record.kind(), record.first_description())
+}
 
-        let key = prefix.to_string();
-        let old_value = result.get(&key);
-        let value: String = if old_value == None {
-            suffix.to_string()
+fn print_suggestions(options: &Options, registry: &Registry) {
+    let suggestions = registry.suggestions(&options.query, MAX_SUGGESTIONS);
+    if suggestions.is_empty() {
+        return;
+    }
+    println!("%%");
+    println!("No match. Did you mean:");
+    for suggestion in suggestions {
+        let matched = format!("{}: {}", suggestion.field, suggestion.value);
+        let title = record_title(suggestion.record, suggestion.field != "Description");
+        if options.use_colors {
+            println!("  \x1b[93m{}\x1b[m  => {}", matched, title);
         } else {
-            old_value.unwrap().to_string() + " ::<sep_tzu>:: " + suffix
+            println!("  {}  => {}", matched, title);
+        }
+    }
+}
+
+fn print_help() {
+    print!("{}", cli::help_text());
+}
+
+fn apply_option(action: Action, value: &str, options: &mut Options, config: &Config) -> Result<(), IanaError> {
+    match action {
+        Action::Shorthand(kind, id) => {
+            // Both would be required, and a record has only one type and one subtag.
+            if let Some((previous_kind, previous_value)) = &options.shorthand {
+                return Err(IanaError::Usage(format!("Only one shorthand per query, got {} \"{}\" and {} \"{}\"",
+                        previous_kind, previous_value, kind, value)));
+            }
+            options.shorthand = Some((kind, value.to_string()));
+            options.query.add("Type", &format!("={}", kind));
+            options.query.add(id, value);
+        }
+        Action::Field(name) => options.query.add(name, value),
+        Action::AnyField => match value.split_once('=') {
+            Some((name, value)) if !name.is_empty() => options.query.add(name, value),
+            _ => return Err(IanaError::Usage(format!("Expected --field <name>=<value>, got: {}", value))),
+        },
+        Action::HasField => options.query.require_field(value),
+        Action::MissingField => options.query.exclude_field(value),
+        Action::Sort => options.sort = Some(value.parse().map_err(IanaError::Usage)?),
+        Action::Limit => {
+            let limit = value.parse().map_err(|_| IanaError::Usage(format!("Invalid --limit: {}", value)))?;
+            options.limit = Some(limit);
+        }
+        Action::Format => options.format = value.parse().map_err(IanaError::Usage)?,
+        Action::Fields => options.fields = value.split(',').map(|name| name.trim().to_string()).collect(),
+        Action::Saved => {
+            let args = config.find_query(value)
+                    .ok_or_else(|| IanaError::Usage(format!("No saved query named \"{}\"", value)))?;
+            if args.iter().any(|arg| arg == "--saved") {
+                return Err(IanaError::Usage(format!("The saved query \"{}\" uses --saved", value)));
+            }
+            args_to_options(args, options, config)?;
+        }
+        Action::Related => options.related = true,
+        Action::DisplayIn => options.display_in = Some(value.to_string()),
+        Action::Reverse => options.reverse = true,
+        Action::ColorAlways => options.use_colors = true,
+        Action::ColorNever => options.use_colors = false,
+        Action::Fold => options.query.fold = true,
+        Action::Fuzzy => options.fuzzy = true,
+        Action::Quiet => options.quiet = true,
+        Action::Help => return Err(IanaError::HelpRequested),
+    }
+    Ok(())
+}
+
+fn args_to_options(args: &[String], options: &mut Options, config: &Config) -> Result<(), IanaError> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        // The aliases of the config file come first, so that they can replace the built-in shorthands.
+        if let Some(alias) = config.find_alias(arg) {
+            for (name, value) in &alias.criteria {
+                options.query.add(name, value);
+            }
+            if let Some(field) = &alias.field {
+                let value = args.next().ok_or_else(|| IanaError::Usage(format!("Missing value for {}", arg)))?;
+                options.query.add(field, value);
+            }
+            continue;
+        }
+        let Some(def) = cli::find_option(arg) else {
+            if arg.starts_with('-') {
+                return Err(IanaError::Usage(format!("Unknown option: {}", arg)));
+            }
+            return Err(IanaError::Usage(format!("Unexpected argument: {}", arg)));
         };
-        result.insert(key, value);
-    }
-    return result;
-}
-
-fn print_record(section: &Vec<String>, to_matchm: &HashMap<String, String>) {
-    let mut found_count = 0;
-    let record = vec_to_map(section);
-    for (key, value) in to_matchm {
-        let z = record.get(key);
-        if z != None {
-            let searched = z.unwrap().to_uppercase();
-            let to_search = value.to_uppercase();
-            if to_search.starts_with("=") {
-                if searched.eq(&to_search[1..]) {
-                    found_count = found_count + 1;
+        let value = if def.takes_value() {
+            args.next().ok_or_else(|| IanaError::Usage(format!("Missing value for {}", arg)))?
+        } else {
+            ""
+        };
+        apply_option(def.action, value, options, config)?;
+    }
+    // dbg!(&options.query);
+    Ok(())
+}
+
+// The options that also make sense for the commands, returns the other arguments.
+fn take_common_options(args: &[String], options: &mut Options, config: &Config) -> Result<Vec<String>, IanaError> {
+    let mut rest: Vec<String> = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match cli::find_option(arg) {
+            Some(def) if def.common => {
+                let value = if def.takes_value() {
+                    args.next().ok_or_else(|| IanaError::Usage(format!("Missing value for {}", arg)))?
+                } else {
+                    ""
+                };
+                apply_option(def.action, value, options, config)?;
+            }
+            _ => rest.push(arg.to_string()),
+        }
+    }
+    Ok(rest)
+}
+
+fn run_compose(data_path:&Path, names: &[String]) -> Result<bool, IanaError> {
+    if names.is_empty() {
+        return Err(IanaError::Usage("compose needs at least a language name".to_string()));
+    }
+    let registry = Registry::load(data_path)?;
+    match compose::compose(&registry, names) {
+        Ok(tag) => {
+            println!("{}", tag);
+            Ok(true)
+        }
+        Err(message) => {
+            println!("{}", message);
+            Ok(false)
+        }
+    }
+}
+
+// The CLDR data is optional, without it the extensions are only checked for syntax.
+fn load_bcp47(data_dir: &Path) -> Result<Option<Bcp47Data>, IanaError> {
+    let dir = data_dir.join(bcp47::BCP47_DIR_NAME);
+    if !dir.is_dir() {
+        return Ok(None);
+    }
+    Ok(Some(Bcp47Data::load_dir(&dir)?))
+}
+
+// Succeeds (returns true) only if all the tags are well-formed and have no warnings.
+fn run_check(options: &Options, data_path:&Path, tags: &[String]) -> Result<bool, IanaError> {
+    if tags.is_empty() {
+        return Err(IanaError::Usage("check needs at least a tag".to_string()));
+    }
+    let registry = Registry::load(data_path)?;
+    let bcp47 = load_bcp47(&options.data_dir)?;
+    let mut all_clean = true;
+    for tag in tags {
+        println!("%%");
+        match lint::check(&registry, tag) {
+            Err(message) => {
+                println!("{}: not well-formed, {}", tag, message);
+                all_clean = false;
+            }
+            Ok(mut report) => {
+                if let Some(bcp47) = &bcp47 {
+                    // The tag is well-formed at this point, the extensions are too.
+                    report.warnings.extend(lint::check_extensions(bcp47, tag).unwrap_or_default());
                 }
-            } else {
-                if searched.contains(&to_search) {
-                    found_count = found_count + 1;
+                if report.warnings.is_empty() {
+                    println!("{}: no warnings", tag);
+                } else {
+                    println!("{}:", tag);
+                    all_clean = false;
+                }
+                for warning in &report.warnings {
+                    if options.use_colors {
+                        println!("  \x1b[91mwarning:\x1b[m {}", warning.message);
+                    } else {
+                        println!("  warning: {}", warning.message);
+                    }
+                    if let Some(record) = warning.record {
+                        print_record(options, "      ", record);
+                    }
+                }
+                if let Some(suggested) = report.suggested {
+                    print_field(options, "  ", "suggested", &suggested);
                 }
             }
         }
     }
-    if found_count == to_matchm.len() {
+    Ok(all_clean)
+}
+
+fn run_explain(options: &Options, data_path:&Path, tags: &[String]) -> Result<bool, IanaError> {
+    if tags.is_empty() {
+        return Err(IanaError::Usage("explain needs at least a tag".to_string()));
+    }
+    let registry = Registry::load(data_path)?;
+    let bcp47 = load_bcp47(&options.data_dir)?;
+    if bcp47.is_none() && !options.quiet {
+        println!("note: no CLDR data in {}, the -u- and -t- keys are not described",
+                options.data_dir.join(bcp47::BCP47_DIR_NAME).display());
+    }
+    let display_names = match &options.display_in {
+        Some(locale) => Some(DisplayNames::load(&options.data_dir.join(display::CLDR_DIR_NAME), locale)?),
+        None => None,
+    };
+    let mut all_valid = true;
+    for tag in tags {
         println!("%%");
-        for line in section {
-            let parts = line.split_once(": ");
-            let prefix = parts.unwrap().0;
-            let postfix = parts.unwrap().1;
-            if get_use_colors() {
-                println!("  \x1b[93m{}:\x1b[m {}", prefix, postfix);
-            } else {
-                println!("  {}: {}", prefix, postfix);
+        let parts = match explain::explain(&registry, bcp47.as_ref(), tag) {
+            Ok(parts) => parts,
+            Err(message) => {
+                println!("{}: not well-formed, {}", tag, message);
+                all_valid = false;
+                continue;
             }
+        };
+        // explain succeeded, so the tag is well-formed (or grandfathered, without a display name).
+        match (&display_names, LanguageTag::parse(tag)) {
+            (Some(names), Ok(parsed)) => println!("{}: {}", tag, names.tag_name(&parsed)),
+            _ => println!("{}:", tag),
+        }
+        let width = parts.iter().map(|part| part.text.len()).max().unwrap_or(0);
+        for part in &parts {
+            let description = if part.description.is_empty() { "?" } else { &part.description };
+            let text = format!("{:width$}", part.text, width = width);
+            print_field(options, "  ", &text, &format!("{} ({})", description, part.kind));
         }
     }
+    Ok(all_valid)
 }
 
-fn print_help() {
-    println!("Usage: iana_info --key <value> [--key <value>] ...");
-    println!("");
-    println!("Find info in the IANA Language Subtag Registry");
-    println!("Filtering:");
-    println!("  -add     --added           <value> // yyyy-MM-dd");
-    println!("  -dep     --deprecated      <value> // yyyy-MM-dd");
-    println!("  -cmt     --comments        <value>");
-    println!("  -d -desc --description     <value>");
-    println!("  -mac     --macrolanguage   <value>");
-    println!("  -pref    --preferred-value <value>");
-    println!("  -px      --prefix          <value>");
-    println!("  -scp     --scope           <value>");
-    println!("      one of: collection, macrolanguage, private-use, special");
-    println!("  -stg     --subtag          <value>");
-    println!("  -ss      --suppress-script <value>");
-    println!("  -tg      --tag             <value>");
-    println!("  -t       --type            <value>");
-    println!("      one of: extlang, grandfathered, language, redundant, region, script, variant");
-    println!("Shorthands (for type=<option> & subtype / type=<value>):");
-    println!("  -el      --extlang         <value>");
-    println!("  -gf      --grandfathered   <value>");
-    println!("  -l       --language        <value>");
-    println!("  -red     --redundant       <value>");
-    println!("  -r       --region          <value>");
-    println!("  -s       --script          <value>");
-    println!("  -v       --variant         <value>");
-    println!("Other:");
-    println!("  --color=always   : force to always use colors");
-    println!("  --color=never    : force to never use colors");
-    println!("  -h       --help  : this help");
-    println!("where the value can be a substring or exact match if it starts with '='");
-    exit(1);
-}
-
-fn args_to_map(args: Vec<String>) -> HashMap<String, String> {
-    let mut result:HashMap<String, String> = HashMap::new();
-    let mut key = "";
-    for arg in &args {
-        match arg.as_str() {
-            // Shorthands
-            "-el" | "--extlang"            => { result.insert("Type".to_string(), "=extlang".to_string());       key = "Subtag" },
-            "-gf" | "--grandfathered"      => { result.insert("Type".to_string(), "=grandfathered".to_string()); key = "Tag" },
-            "-l" | "--language"            => { result.insert("Type".to_string(), "=language".to_string());      key = "Subtag" },
-            "-red" | "--redundant"         => { result.insert("Type".to_string(), "=redundant".to_string());     key = "Tag" },
-            "-r" | "--region"              => { result.insert("Type".to_string(), "=region".to_string());        key = "Subtag" },
-            "-s" | "--script"              => { result.insert("Type".to_string(), "=script".to_string());        key = "Subtag" },
-            "-v" | "--variant"             => { result.insert("Type".to_string(), "=variant".to_string());       key = "Subtag" },
-
-            "-add"   | "--added"           => key = "Added",
-            "-dep"   | "--deprecated"      => key = "Deprecated",
-            "-cmt"   | "--comments"        => key = "Comments",
-            "-d" | "-desc" | "--description" => key = "Description",
-            "-mac"   | "--macrolanguage"   => key = "Macrolanguage",
-            "-pref"  | "--preferred-value" => key = "Preferred-Value",
-            "-px"    | "--prefix"          => key = "Prefix",
-            "-scp"   | "--scope"           => key = "Scope",
-            "-stg"   | "--subtag"          => key = "Subtag",
-            "-ss"    | "--suppress-script" => key = "Suppress-Script",
-            "-tg"    | "--tag"             => key = "Tag",
-            "-t"     | "--type"            => key = "Type",
-            "--color=always"               => set_use_colors(true),
-            "--color=never"                => set_use_colors(false),
-            "-h"     | "--help"            => print_help(),
-            _ => {
-                if !key.is_empty() {
-                    result.insert(key.to_string(), arg.to_string());
-                    key = "";
-                } else {
-                    if arg.starts_with('-') {
-                        print_help();
-                    }
-                }
-            },
+// Prints the maximized (or minimized) tags, one per line.
+fn run_likely(options: &Options, tags: &[String], maximize: bool) -> Result<bool, IanaError> {
+    if tags.is_empty() {
+        let command = if maximize { "maximize" } else { "minimize" };
+        return Err(IanaError::Usage(format!("{} needs at least a tag", command)));
+    }
+    let path = options.data_dir.join(display::CLDR_DIR_NAME).join("supplemental").join(likely::LIKELY_SUBTAGS_FILE_NAME);
+    let likely = LikelySubtags::load(&path)?;
+    let mut all_done = true;
+    for tag in tags {
+        let parsed = match LanguageTag::parse(tag) {
+            Ok(parsed) => parsed,
+            Err(message) => {
+                println!("{}: not well-formed, {}", tag, message);
+                all_done = false;
+                continue;
+            }
         };
+        let result = if maximize { likely.maximize(&parsed) } else { likely.minimize(&parsed) };
+        match result {
+            Some(result) => println!("{}", result),
+            None => {
+                println!("{}: no likely subtags", tag);
+                all_done = false;
+            }
+        }
     }
-    // dbg!(&result);
-    return result;
+    Ok(all_done)
 }
 
-fn get_data_path() -> PathBuf {
+fn iso639_scope(scope: &str) -> &str {
+    match scope {
+        "I" => "individual",
+        "M" => "macrolanguage",
+        "S" => "special",
+        _ => scope,
+    }
+}
 
-    // let xx = env::current_exe().unwrap().parent().unwrap().join(default_file_name);
-    // dbg!(&xx);
-    // dbg!(&xx.exists());
+fn iso639_type(language_type: &str) -> &str {
+    match language_type {
+        "A" => "ancient",
+        "C" => "constructed",
+        "E" => "extinct",
+        "H" => "historical",
+        "L" => "living",
+        "S" => "special",
+        _ => language_type,
+    }
+}
 
-    // let xx = env::current_exe().unwrap().parent().unwrap().join("iana").join(default_file_name);
-    // dbg!(&xx);
-    // dbg!(&xx.exists());
+// Succeeds (returns true) only if all the codes are known.
+fn run_iso639(options: &Options, data_path:&Path, codes: &[String]) -> Result<bool, IanaError> {
+    if codes.is_empty() {
+        return Err(IanaError::Usage("iso639 needs at least a code".to_string()));
+    }
+    let registry = Registry::load(data_path)?;
+    let table = Iso639Table::load_dir(&options.data_dir)?;
+    if table.is_empty() {
+        return Err(IanaError::InvalidData {
+            path: options.data_dir.join(iana_info::iso639::ISO_639_3_FILE_NAME),
+            message: "no ISO 639 tables found".to_string(),
+        });
+    }
+    let mut all_found = true;
+    for code in codes {
+        println!("%%");
+        let Some(crosswalk) = table.crosswalk(&registry, code) else {
+            println!("{}: unknown ISO 639 code", code);
+            all_found = false;
+            continue;
+        };
+        println!("{}:", code);
+        let found = crosswalk.code;
+        let fields = [
+            ("639-3", found.part3.as_str()),
+            ("639-2/B", found.part2b.as_str()),
+            ("639-2/T", found.part2t.as_str()),
+            ("639-1", found.part1.as_str()),
+            ("Name", found.name.as_str()),
+            ("Scope", iso639_scope(&found.scope)),
+            ("Type", iso639_type(&found.language_type)),
+            ("Macrolanguage", crosswalk.macrolanguage.as_deref().unwrap_or_default()),
+        ];
+        for (prefix, postfix) in fields {
+            if !postfix.is_empty() {
+                print_field(options, "  ", prefix, postfix);
+            }
+        }
+        let bcp47 = match crosswalk.record {
+            None => format!("{} (not in the registry)", crosswalk.subtag),
+            Some(record) if record.has("Preferred-Value") =>
+                format!("{} (deprecated, use {})", crosswalk.subtag, record.field("Preferred-Value")),
+            Some(_) => crosswalk.subtag,
+        };
+        print_field(options, "  ", "BCP 47", &bcp47);
+    }
+    Ok(all_found)
+}
 
-    // let env_path = env::var("IANA_FILE");
-    // if env_path.is_ok() {
-    //     let wtf = env_path.unwrap();
-    //     let xx = Path::new(&wtf);
-    //     dbg!(&xx);
-    //     dbg!(&xx.exists());
-    // }
+fn run_completions(shells: &[String]) -> Result<bool, IanaError> {
+    let script = match shells {
+        [shell] if shell == "bash" => completions::bash(),
+        [shell] if shell == "zsh" => completions::zsh(),
+        [shell] if shell == "fish" => completions::fish(),
+        _ => return Err(IanaError::Usage(format!("completions needs one of: {}", cli::SHELLS.join(", ")))),
+    };
+    print!("{}", script);
+    Ok(true)
+}
 
-    return env::current_exe().unwrap()
-            .parent().unwrap()
-            .join("udata")
-            .join(DEFAULT_IANA_FILE_NAME);
+// Called by the completion scripts, prints the values of an option starting with the prefix.
+fn run_complete(data_path:&Path, config: &Config, args: &[String]) -> Result<bool, IanaError> {
+    let (name, prefix) = match args {
+        [name] => (name, ""),
+        [name, prefix] => (name, prefix.as_str()),
+        _ => return Err(IanaError::Usage("complete needs an option and a prefix".to_string())),
+    };
+    let Some(def) = cli::find_option(name) else {
+        return Ok(false);
+    };
+    let registry = Registry::load(data_path)?;
+    let values = completions::complete_values(&registry, config, def.completion, prefix);
+    for value in &values {
+        println!("{}", value);
+    }
+    Ok(!values.is_empty())
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
+fn run_search(options: &Options, data_path:&Path) -> Result<bool, IanaError> {
+    let registry = Registry::load(data_path)?;
+    if !options.quiet {
+        println!("File-Date: {}", registry.file_date);
+    }
 
-    let data_path = get_data_path();
+    let mut found = if options.fuzzy {
+        registry.fuzzy_search(&options.query, options.limit.unwrap_or(MAX_FUZZY_RESULTS))
+    } else {
+        registry.search(&options.query)
+    };
+    if let Some(sort) = options.sort {
+        search::sort_records(&mut found, sort, options.reverse);
+    } else if options.reverse {
+        found.reverse();
+    }
+    if let Some(limit) = options.limit {
+        found.truncate(limit);
+    }
+    let relations = options.related.then(|| Relations::new(&registry));
+    for record in &found {
+        match options.format {
+            Format::Full => {
+                println!("%%");
+                print_selected_fields(options, "  ", record);
+            }
+            Format::Brief => println!("{}", record_title(record, true)),
+        }
+        if let Some(relations) = &relations {
+            print_related(options, relations, record);
+        }
+    }
+    if found.is_empty() {
+        print_suggestions(options, &registry);
+    }
+    if !options.quiet {
+        println!("%%");
+        if options.use_colors {
+            println!("\x1b[32mDONE!\x1b[m");
+        } else {
+            println!("DONE!");
+        }
+    }
+    // Something is always ranked in fuzzy mode, the exit code still tells if the query matched.
+    if options.fuzzy {
+        return Ok(!registry.search(&options.query).is_empty());
+    }
+    Ok(!found.is_empty())
+}
+
+// The help, the completion scripts and the man page don't depend on the config file,
+// a broken one must not prevent reading how to fix it.
+fn needs_config(args: &[String]) -> bool {
+    match args.get(1).map(String::as_str) {
+        Some("completions") | Some("man") => false,
+        // The prefix to complete could be "-h".
+        Some("complete") => true,
+        _ => !args.iter().skip(1).any(|arg| cli::find_option(arg).is_some_and(|def| def.action == Action::Help)),
+    }
+}
+
+// Returns true if something matched (or the command succeeded).
+fn run(args: &[String], options: &mut Options) -> Result<bool, IanaError> {
+    let config = match config::default_config_path().map(|path| Config::load(&path)) {
+        Some(Ok(config)) => config,
+        Some(Err(err)) if !needs_config(args) => {
+            eprintln!("iana_info: warning: {}", err);
+            Config::default()
+        }
+        Some(Err(err)) => return Err(err),
+        None => Config::default(),
+    };
+    // The command line wins over the config file.
+    if let Some(data_dir) = &config.data_dir {
+        options.data_dir = data_dir.clone();
+    }
+    if let Some(use_colors) = config.use_colors {
+        options.use_colors = use_colors;
+    }
+    options.format = config.format.unwrap_or_default();
+    options.fields = config.fields.clone();
+
+    let data_path = options.data_dir.join(registry::DEFAULT_IANA_FILE_NAME);
     // let data_path = Path::new(&args[0]).parent().unwrap().join("language-subtag-registry");
     // dbg!(&data_path);
 
-    if atty::is(Stream::Stdout) {
-        set_use_colors(true);
-    } else {
-        set_use_colors(false);
+    if let Some(command) = args.get(1).and_then(|name| cli::find_command(name)) {
+        // The prefix to complete could be anything, even "-h".
+        if command.name == "complete" {
+            return run_complete(&data_path, &config, &args[2..]);
+        }
+        let rest = take_common_options(&args[2..], options, &config)?;
+        if options.display_in.is_some() && command.name != "explain" {
+            return Err(IanaError::Usage("--display-in only works with explain".to_string()));
+        }
+        return match command.name {
+            "compose" => run_compose(&data_path, &rest),
+            "check" => run_check(options, &data_path, &rest),
+            "explain" => run_explain(options, &data_path, &rest),
+            "iso639" => run_iso639(options, &data_path, &rest),
+            "maximize" => run_likely(options, &rest, true),
+            "minimize" => run_likely(options, &rest, false),
+            "completions" => run_completions(&rest),
+            "man" => {
+                if !rest.is_empty() {
+                    return Err(IanaError::Usage(format!("man takes no arguments, got: {}", rest.join(" "))));
+                }
+                print!("{}", completions::man());
+                Ok(true)
+            }
+            name => unreachable!("no handler for the command \"{}\" of cli::COMMANDS", name),
+        };
     }
 
-    let to_matchm = args_to_map(args);
-    if to_matchm.is_empty() {
-        print_help();
+    args_to_options(&args[1..], options, &config)?;
+    if options.display_in.is_some() {
+        return Err(IanaError::Usage("--display-in only works with explain".to_string()));
+    }
+    if options.query.is_empty() {
+        return Err(IanaError::Usage("Nothing to search for".to_string()));
     }
 
-    // let mut to_matchm:HashMap<String, String> = HashMap::new();
-    // to_matchm.insert("Subtag".to_string(), args.get(1).unwrap().to_string());
+    run_search(options, &data_path)
+}
 
-    let lines = read_lines(&data_path);
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
 
-    // Iterate over the lines of the file, and in this case print them.
-    let mut current_rec: Vec<String> = Vec::with_capacity(8);
-    for line in lines {
-        let line_content = line.unwrap();
-        if line_content.starts_with("File-Date:") {
-            println!("{}", line_content);
-        } else if line_content.starts_with("%%") {
-            print_record(&current_rec, &to_matchm);
-            current_rec = Vec::with_capacity(8);
-        } else if line_content.starts_with("  ") {
-            let long_line = current_rec.pop().unwrap().to_owned() + &line_content[1..];
-            current_rec.push(long_line);
-        } else {
-            current_rec.push(line_content);
+    let mut options = Options {
+        use_colors: atty::is(Stream::Stdout),
+        data_dir: registry::data_dir(),
+        ..Default::default()
+    };
+
+    match run(&args, &mut options) {
+        Ok(true) => ExitCode::from(EXIT_MATCH),
+        Ok(false) => ExitCode::from(EXIT_NO_MATCH),
+        Err(IanaError::HelpRequested) => {
+            print_help();
+            ExitCode::from(EXIT_MATCH)
+        }
+        Err(IanaError::Usage(message)) => {
+            eprintln!("iana_info: {}", message);
+            eprintln!("Try 'iana_info --help' for more information.");
+            ExitCode::from(EXIT_USAGE)
+        }
+        Err(err) => {
+            eprintln!("iana_info: {}", err);
+            ExitCode::from(EXIT_DATA_ERROR)
         }
     }
-    print_record(&current_rec, &to_matchm);
-    println!("%%");
-    if get_use_colors() {
-        println!("\x1b[32mDONE!\x1b[m");
-    } else {
-        println!("DONE!");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, IanaError> {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        let mut options = Options::default();
+        args_to_options(&args, &mut options, &Config::default()).map(|_| options)
+    }
+
+    #[test]
+    fn test_shorthands() {
+        let options = parse(&["-l", "fr", "-d", "French"]).unwrap();
+        assert_eq!(options.query.criteria, vec![
+            ("Type".to_string(), "=language".to_string()),
+            ("Subtag".to_string(), "fr".to_string()),
+            ("Description".to_string(), "French".to_string()),
+        ]);
+        assert!(matches!(parse(&["-l", "fr", "-r", "FR"]), Err(IanaError::Usage(_))));
+        assert!(matches!(parse(&["-l", "fr", "--language", "de"]), Err(IanaError::Usage(_))));
     }
 }
 
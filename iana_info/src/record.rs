@@ -0,0 +1,70 @@
+/// One record of the registry: the `Field-Name: value` pairs between two `%%` lines.
+///
+/// The fields are kept in file order, and a field can appear more than once
+/// (`Description`, `Prefix`, `Comments`, ...).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Record {
+    fields: Vec<(String, String)>,
+}
+
+impl Record {
+    pub fn new() -> Record {
+        Record::default()
+    }
+
+    pub fn push(&mut self, name: &str, value: &str) {
+        self.fields.push((name.to_string(), value.to_string()));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// All the fields, in file order.
+    pub fn fields(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.fields.iter().map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// The first value of a field. Field names are case insensitive.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.fields().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, value)| value)
+    }
+
+    /// The first value of a field, or an empty string if the record doesn't have it.
+    pub fn field(&self, name: &str) -> &str {
+        self.get(name).unwrap_or("")
+    }
+
+    /// All the values of a (possibly repeated) field.
+    pub fn values(&self, name: &str) -> Vec<&str> {
+        self.fields().filter(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, value)| value).collect()
+    }
+
+    pub fn has(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// The `Type` of the record (`language`, `script`, `region`, ...).
+    pub fn kind(&self) -> &str {
+        self.field("Type")
+    }
+
+    /// The `Subtag`, or the `Tag` for grandfathered and redundant records.
+    pub fn id(&self) -> &str {
+        self.get("Subtag").or(self.get("Tag")).unwrap_or("")
+    }
+
+    pub fn first_description(&self) -> &str {
+        self.field("Description")
+    }
+
+    pub fn is_deprecated(&self) -> bool {
+        self.has("Deprecated")
+    }
+}
+
+impl FromIterator<(String, String)> for Record {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Record {
+        Record { fields: iter.into_iter().collect() }
+    }
+}
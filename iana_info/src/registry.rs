@@ -0,0 +1,164 @@
+use std::env;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use crate::error::IanaError;
+use crate::record::Record;
+
+pub const DEFAULT_IANA_FILE_NAME:&str = "language-subtag-registry";
+
+/// The IANA Language Subtag Registry, as loaded from a `language-subtag-registry` file.
+#[derive(Debug, Default)]
+pub struct Registry {
+    /// The value of the `File-Date` header (yyyy-MM-dd).
+    pub file_date: String,
+    pub records: Vec<Record>,
+}
+
+/// The `udata` folder next to the executable, where all the data files live.
+pub fn data_dir() -> PathBuf {
+    let exe_dir = env::current_exe().ok()
+            .and_then(|exe| exe.parent().map(Path::to_path_buf))
+            .unwrap_or_default();
+    exe_dir.join("udata")
+}
+
+/// The registry file in the `udata` folder next to the executable.
+pub fn default_data_path() -> PathBuf {
+    data_dir().join(DEFAULT_IANA_FILE_NAME)
+}
+
+impl Registry {
+    pub fn load(data_path: &Path) -> Result<Registry, IanaError> {
+        let file = File::open(data_path)
+                .map_err(|source| IanaError::Io { path: data_path.to_path_buf(), source })?;
+        Registry::parse(BufReader::new(file), data_path)
+    }
+
+    /// Parses the registry format (RFC 5646, section 3.1.1).
+    /// The path is only used in the error messages.
+    pub fn parse<R: BufRead>(reader: R, data_path: &Path) -> Result<Registry, IanaError> {
+        let malformed = |line_number: usize, line: String, reason: &'static str| IanaError::Malformed {
+            path: data_path.to_path_buf(), line_number, line, reason
+        };
+
+        let mut result = Registry::default();
+        // Fields are collected as lines first, because of the continuation lines.
+        let mut current_rec: Vec<String> = Vec::with_capacity(8);
+        let mut records: Vec<Vec<String>> = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line_content = line.map_err(|source| IanaError::Io { path: data_path.to_path_buf(), source })?;
+            if let Some(file_date) = line_content.strip_prefix("File-Date: ") {
+                result.file_date = file_date.to_string();
+            } else if line_content.starts_with("%%") {
+                records.push(current_rec);
+                current_rec = Vec::with_capacity(8);
+            } else if line_content.starts_with("  ") {
+                match current_rec.pop() {
+                    Some(previous) => current_rec.push(previous + &line_content[1..]),
+                    None => return Err(malformed(index + 1, line_content, "continuation line without a field")),
+                }
+            } else if line_content.trim().is_empty() {
+                continue;
+            } else if line_content.contains(": ") {
+                current_rec.push(line_content);
+            } else {
+                return Err(malformed(index + 1, line_content, "expected \"Field-Name: value\""));
+            }
+        }
+        records.push(current_rec);
+
+        for lines in records {
+            let mut record = Record::new();
+            for line in &lines {
+                if let Some((name, value)) = line.split_once(": ") {
+                    record.push(name, value);
+                }
+            }
+            if !record.is_empty() {
+                result.records.push(record);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Finds the record of the given type with the given `Subtag` (or `Tag`), ignoring case.
+    /// The private use ranges (`qaa..qtz`, `Qaaa..Qabx`...) match all the subtags they contain.
+    pub fn find(&self, kind: &str, subtag: &str) -> Option<&Record> {
+        self.records.iter().find(|record| record.kind() == kind && id_matches(record.id(), subtag))
+    }
+}
+
+// The registry ranges have ends of the same length, and the subtags compare in alphabetical order.
+fn id_matches(id: &str, subtag: &str) -> bool {
+    match id.split_once("..") {
+        Some((first, last)) if first.len() == subtag.len() && last.len() == subtag.len() => {
+            let subtag = subtag.to_ascii_lowercase();
+            first.to_ascii_lowercase() <= subtag && subtag <= last.to_ascii_lowercase()
+        }
+        Some(_) => false,
+        None => id.eq_ignore_ascii_case(subtag),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "File-Date: 2023-10-16
+%%
+Type: language
+Subtag: en
+Description: English
+Added: 2005-10-16
+Suppress-Script: Latn
+%%
+Type: variant
+Subtag: 1994
+Description: Standardized Resian orthography
+Added: 2007-07-28
+Prefix: sl-rozaj
+Prefix: sl-rozaj-biske
+Comments: For standardized Resian an orthography was published in
+  1994.
+";
+
+    #[test]
+    fn test_parse() {
+        let registry = Registry::parse(SAMPLE.as_bytes(), Path::new("sample")).unwrap();
+        assert_eq!(registry.file_date, "2023-10-16");
+        assert_eq!(registry.records.len(), 2);
+        let variant = registry.find("variant", "1994").unwrap();
+        assert_eq!(variant.values("Prefix"), vec!["sl-rozaj", "sl-rozaj-biske"]);
+        assert_eq!(variant.field("Comments"), "For standardized Resian an orthography was published in 1994.");
+        assert_eq!(registry.find("language", "EN").unwrap().field("Suppress-Script"), "Latn");
+        assert!(registry.find("region", "en").is_none());
+    }
+
+    #[test]
+    fn test_find_range() {
+        let sample = "%%\nType: language\nSubtag: qaa..qtz\nDescription: Private use\n\
+                %%\nType: script\nSubtag: Qaaa..Qabx\nDescription: Private use\n\
+                %%\nType: region\nSubtag: XA..XZ\nDescription: Private use\n";
+        let registry = Registry::parse(sample.as_bytes(), Path::new("sample")).unwrap();
+        assert_eq!(registry.find("language", "qaa").unwrap().id(), "qaa..qtz");
+        assert!(registry.find("language", "QTZ").is_some());
+        assert!(registry.find("language", "qua").is_none());
+        assert!(registry.find("language", "qa").is_none());
+        assert!(registry.find("script", "Qaab").is_some());
+        assert!(registry.find("script", "Qaby").is_none());
+        assert!(registry.find("region", "XA").is_some());
+        assert!(registry.find("region", "xz").is_some());
+        assert!(registry.find("region", "YA").is_none());
+    }
+
+    #[test]
+    fn test_parse_malformed() {
+        let result = Registry::parse("%%\nType: language\nbroken\n".as_bytes(), Path::new("sample"));
+        match result {
+            Err(IanaError::Malformed { line_number, .. }) => assert_eq!(line_number, 3),
+            _ => panic!("expected a Malformed error"),
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use crate::record::Record;
+use crate::registry::Registry;
+use crate::tag::LanguageTag;
+
+/// A record referenced by another one (or referencing it, if `reverse`).
+#[derive(Debug)]
+pub struct Relation<'a> {
+    /// The field holding the reference: `Preferred-Value`, `Macrolanguage`, `Suppress-Script` or `Prefix`.
+    pub field: &'static str,
+    /// True if it is the related record that has the field, pointing to the one we started from.
+    pub reverse: bool,
+    pub record: &'a Record,
+}
+
+// Each record is only listed once, and never the record we started from.
+fn add_relations<'a>(result: &mut Vec<Relation<'a>>, start: &Record, field: &'static str, reverse: bool,
+        related: Vec<&'a Record>) {
+    for record in related {
+        let known = result.iter().any(|relation| std::ptr::eq(relation.record, record));
+        if !known && !std::ptr::eq(record, start) {
+            result.push(Relation { field, reverse, record });
+        }
+    }
+}
+
+/// Finds the related records, for many records of the same registry.
+pub struct Relations<'a> {
+    registry: &'a Registry,
+    // The only records that can point back to another one.
+    with_preferred_value: Vec<&'a Record>,
+}
+
+impl<'a> Relations<'a> {
+    pub fn new(registry: &'a Registry) -> Relations<'a> {
+        let with_preferred_value = registry.records.iter().filter(|record| record.has("Preferred-Value")).collect();
+        Relations { registry, with_preferred_value }
+    }
+
+    /// The records a record references, followed by the records whose `Preferred-Value` is this record.
+    pub fn of(&self, record: &Record) -> Vec<Relation<'a>> {
+        let registry = self.registry;
+        let mut result: Vec<Relation> = Vec::new();
+        let mut add = |field, reverse, related| add_relations(&mut result, record, field, reverse, related);
+
+        add("Preferred-Value", false, registry.preferred_records(record));
+        for macrolanguage in record.values("Macrolanguage") {
+            add("Macrolanguage", false, registry.find("language", macrolanguage).into_iter().collect());
+        }
+        for script in record.values("Suppress-Script") {
+            add("Suppress-Script", false, registry.find("script", script).into_iter().collect());
+        }
+        for prefix in record.values("Prefix") {
+            add("Prefix", false, registry.subtag_records(prefix));
+        }
+
+        // Cheap test first, most records don't have a Preferred-Value mentioning this one.
+        let id = record.id();
+        let pointing: Vec<&Record> = self.with_preferred_value.iter()
+            .filter(|other| other.field("Preferred-Value").split('-').any(|part| part.eq_ignore_ascii_case(id))
+                    || other.field("Preferred-Value").eq_ignore_ascii_case(id))
+            .filter(|other| registry.preferred_records(other).iter().any(|target| std::ptr::eq(*target, record)))
+            .copied()
+            .collect();
+        add("Preferred-Value", true, pointing);
+        result
+    }
+}
+
+impl Registry {
+    /// The records of the subtags in a tag (the ones that are registered).
+    pub fn subtag_records(&self, tag: &str) -> Vec<&Record> {
+        let Ok(parsed) = LanguageTag::parse(tag) else {
+            return Vec::new();
+        };
+        let mut subtags: Vec<(&str, &str)> = vec![("language", &parsed.language)];
+        subtags.extend(parsed.extlangs.iter().map(|extlang| ("extlang", extlang.as_str())));
+        subtags.extend(parsed.script.iter().map(|script| ("script", script.as_str())));
+        subtags.extend(parsed.region.iter().map(|region| ("region", region.as_str())));
+        subtags.extend(parsed.variants.iter().map(|variant| ("variant", variant.as_str())));
+        subtags.iter().filter_map(|(kind, subtag)| self.find(kind, subtag)).collect()
+    }
+
+    /// The record of a grandfathered or redundant tag, or else the records of its subtags.
+    pub fn tag_records(&self, tag: &str) -> Vec<&Record> {
+        for kind in ["grandfathered", "redundant"] {
+            if let Some(record) = self.find(kind, tag) {
+                return vec![record];
+            }
+        }
+        self.subtag_records(tag)
+    }
+
+    // What the Preferred-Value of a record points to: a subtag of the same type
+    // (a language for extlangs), or a whole tag for grandfathered and redundant tags.
+    fn preferred_records(&self, record: &Record) -> Vec<&Record> {
+        let Some(value) = record.get("Preferred-Value") else {
+            return Vec::new();
+        };
+        match record.kind() {
+            "grandfathered" | "redundant" => self.tag_records(value),
+            "extlang" => self.find("language", value).into_iter().collect(),
+            kind => self.find(kind, value).into_iter().collect(),
+        }
+    }
+
+    /// The records related to a record, see `Relations` to do this for many records.
+    pub fn related(&self, record: &Record) -> Vec<Relation<'_>> {
+        Relations::new(self).of(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    const SAMPLE: &str = "%%
+Type: language
+Subtag: ro
+Description: Romanian
+Suppress-Script: Latn
+%%
+Type: language
+Subtag: mo
+Description: Moldavian
+Deprecated: 2008-11-22
+Preferred-Value: ro
+%%
+Type: script
+Subtag: Latn
+Description: Latin
+%%
+Type: language
+Subtag: zh
+Description: Chinese
+Scope: macrolanguage
+%%
+Type: language
+Subtag: cmn
+Description: Mandarin Chinese
+Macrolanguage: zh
+%%
+Type: extlang
+Subtag: cmn
+Description: Mandarin Chinese
+Preferred-Value: cmn
+Prefix: zh
+Macrolanguage: zh
+%%
+Type: redundant
+Tag: zh-cmn
+Description: Mandarin Chinese
+Deprecated: 2009-07-29
+Preferred-Value: cmn
+";
+
+    fn summary(relations: &[Relation]) -> Vec<String> {
+        relations.iter()
+            .map(|relation| format!("{}{} {}", if relation.reverse { "<" } else { ">" }, relation.field, relation.record.id()))
+            .collect()
+    }
+
+    #[test]
+    fn test_related() {
+        let registry = Registry::parse(SAMPLE.as_bytes(), Path::new("sample")).unwrap();
+        let mo = registry.find("language", "mo").unwrap();
+        assert_eq!(summary(&registry.related(mo)), vec![">Preferred-Value ro"]);
+        let ro = registry.find("language", "ro").unwrap();
+        assert_eq!(summary(&registry.related(ro)), vec![">Suppress-Script Latn", "<Preferred-Value mo"]);
+        let extlang = registry.find("extlang", "cmn").unwrap();
+        assert_eq!(summary(&registry.related(extlang)), vec![">Preferred-Value cmn", ">Macrolanguage zh"]);
+        let cmn = registry.find("language", "cmn").unwrap();
+        assert_eq!(summary(&registry.related(cmn)), vec![">Macrolanguage zh", "<Preferred-Value cmn", "<Preferred-Value zh-cmn"]);
+        assert_eq!(registry.tag_records("zh-cmn").len(), 1);
+        assert_eq!(registry.subtag_records("zh-cmn").len(), 2);
+        assert_eq!(registry.tag_records("ro-Latn").len(), 2);
+    }
+}
@@ -0,0 +1,275 @@
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use crate::fold;
+use crate::fuzzy;
+use crate::record::Record;
+use crate::registry::Registry;
+
+/// What to search for: (field name, value) pairs, and fields that must be present or absent.
+///
+/// A value starting with `=` has to match exactly, otherwise it can be a
+/// substring. Matching is case insensitive, and also accent insensitive
+/// when `fold` is set. For repeated fields, matching any of the values is enough.
+/// A record matches if it satisfies all the conditions.
+/// Any field name works, not only the ones currently used by IANA.
+#[derive(Clone, Debug, Default)]
+pub struct Query {
+    pub criteria: Vec<(String, String)>,
+    pub has_fields: Vec<String>,
+    pub missing_fields: Vec<String>,
+    pub fold: bool,
+}
+
+/// A "did you mean" proposal for a query that matched nothing.
+pub struct Suggestion<'a> {
+    pub distance: usize,
+    /// The field that was close to the query, and its value.
+    pub field: String,
+    pub value: String,
+    pub record: &'a Record,
+}
+
+impl Query {
+    pub fn new() -> Query {
+        Query::default()
+    }
+
+    pub fn add(&mut self, field: &str, value: &str) {
+        self.criteria.push((field.to_string(), value.to_string()));
+    }
+
+    /// Only match records that have the field (with any value).
+    pub fn require_field(&mut self, field: &str) {
+        self.has_fields.push(field.to_string());
+    }
+
+    /// Only match records that don't have the field.
+    pub fn exclude_field(&mut self, field: &str) {
+        self.missing_fields.push(field.to_string());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.criteria.is_empty() && self.has_fields.is_empty() && self.missing_fields.is_empty()
+    }
+
+    fn normalize(&self, text: &str) -> String {
+        if self.fold {
+            fold::fold(text)
+        } else {
+            text.to_uppercase()
+        }
+    }
+
+    fn value_matches(&self, found: &str, wanted: &str) -> bool {
+        let found = self.normalize(found);
+        match wanted.strip_prefix('=') {
+            Some(exact) => found == self.normalize(exact),
+            None => found.contains(&self.normalize(wanted)),
+        }
+    }
+
+    fn has_fields_matching(&self, record: &Record) -> bool {
+        self.has_fields.iter().all(|field| record.has(field))
+            && !self.missing_fields.iter().any(|field| record.has(field))
+    }
+
+    pub fn matches(&self, record: &Record) -> bool {
+        self.has_fields_matching(record) && self.criteria.iter().all(|(field, wanted)| {
+            record.values(field).iter().any(|found| self.value_matches(found, wanted))
+        })
+    }
+
+    // The Type criterion (usually set by a shorthand like --language) stays a hard
+    // filter in fuzzy mode, the types are a closed set and nobody misspells them.
+    // The same goes for the presence or absence of fields.
+    fn type_matches(&self, record: &Record) -> bool {
+        self.has_fields_matching(record) && self.criteria.iter()
+            .filter(|(field, _)| field.eq_ignore_ascii_case("Type"))
+            .all(|(_, wanted)| record.kind().eq_ignore_ascii_case(wanted.trim_start_matches('=')))
+    }
+
+    fn fuzzy_criteria(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.criteria.iter()
+            .filter(|(field, _)| !field.eq_ignore_ascii_case("Type"))
+            .map(|(field, value)| (field.as_str(), value.as_str()))
+    }
+}
+
+/// The orders in which search results can be listed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SortKey {
+    Subtag,
+    Added,
+    Deprecated,
+    Description,
+    Type,
+}
+
+impl FromStr for SortKey {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<SortKey, String> {
+        match name.to_ascii_lowercase().as_str() {
+            "subtag" | "tag" => Ok(SortKey::Subtag),
+            "added" => Ok(SortKey::Added),
+            "deprecated" => Ok(SortKey::Deprecated),
+            "description" => Ok(SortKey::Description),
+            "type" => Ok(SortKey::Type),
+            _ => Err(format!("Unknown sort key: {} (expected subtag, added, deprecated, description or type)", name)),
+        }
+    }
+}
+
+impl SortKey {
+    fn value(self, record: &Record) -> Option<String> {
+        match self {
+            SortKey::Subtag => Some(record.id().to_ascii_lowercase()),
+            SortKey::Added => record.get("Added").map(str::to_string),
+            SortKey::Deprecated => record.get("Deprecated").map(str::to_string),
+            SortKey::Description => record.get("Description").map(fold::fold),
+            SortKey::Type => Some(record.kind().to_string()),
+        }
+    }
+}
+
+/// Sorts records (stable), `reverse` for descending order.
+///
+/// Records without the field (e.g. not deprecated when sorting by `Deprecated`)
+/// always come last, in both directions.
+pub fn sort_records<'a>(records: &mut Vec<&'a Record>, key: SortKey, reverse: bool) {
+    let mut keyed: Vec<(Option<String>, &'a Record)> = records.iter()
+        .map(|record| (key.value(record), *record))
+        .collect();
+    keyed.sort_by(|(a, _), (b, _)| match (a, b) {
+        (Some(a), Some(b)) => if reverse { b.cmp(a) } else { a.cmp(b) },
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+    *records = keyed.into_iter().map(|(_, record)| record).collect();
+}
+
+// Distance from the query to the closest value of the queried field.
+fn field_distance<'a>(record: &'a Record, field: &str, query: &str) -> Option<(usize, &'a str)> {
+    record.values(field).into_iter()
+        .map(|value| (fuzzy::distance(query, value), value))
+        .min_by_key(|(distance, _)| *distance)
+}
+
+impl Registry {
+    /// All the records matching the query, in file order.
+    pub fn search(&self, query: &Query) -> Vec<&Record> {
+        self.records.iter().filter(|record| query.matches(record)).collect()
+    }
+
+    /// The records closest to the query (by edit distance), best first.
+    pub fn fuzzy_search(&self, query: &Query, limit: usize) -> Vec<&Record> {
+        let mut ranked: Vec<(usize, usize)> = Vec::new();
+        for (index, record) in self.records.iter().enumerate() {
+            if !query.type_matches(record) {
+                continue;
+            }
+            let mut total = 0;
+            // Only the presence / absence of fields was asked for, nothing to rank.
+            let mut found_any = query.criteria.is_empty();
+            for (field, wanted) in query.fuzzy_criteria() {
+                match field_distance(record, field, wanted) {
+                    Some((distance, _)) => { total += distance; found_any = true },
+                    None => total += wanted.chars().count(),
+                }
+            }
+            if found_any {
+                ranked.push((total, index));
+            }
+        }
+        ranked.sort();
+        ranked.iter().take(limit).map(|(_, index)| &self.records[*index]).collect()
+    }
+
+    /// Close matches for a query that found nothing, best first.
+    pub fn suggestions(&self, query: &Query, limit: usize) -> Vec<Suggestion<'_>> {
+        // (distance, not a prefix of the query, suggestion)
+        let mut candidates: Vec<(usize, bool, Suggestion)> = Vec::new();
+        for record in &self.records {
+            if !query.type_matches(record) {
+                continue;
+            }
+            for (field, wanted) in query.fuzzy_criteria() {
+                if let Some((distance, value)) = field_distance(record, field, wanted) {
+                    if distance <= fuzzy::max_suggestion_distance(wanted) {
+                        let wanted = fold::fold(wanted.trim_start_matches('='));
+                        let folded = fold::fold(value);
+                        let is_prefix = wanted.starts_with(&folded) || folded.starts_with(&wanted);
+                        candidates.push((distance, !is_prefix, Suggestion {
+                            distance, field: field.to_string(), value: value.to_string(), record
+                        }));
+                    }
+                }
+            }
+        }
+        candidates.sort_by(|a, b| (a.0, a.1, &a.2.field, &a.2.value).cmp(&(b.0, b.1, &b.2.field, &b.2.value)));
+        let mut result: Vec<Suggestion> = Vec::new();
+        for (_, _, suggestion) in candidates {
+            if result.len() < limit && !result.iter().any(|s| std::ptr::eq(s.record, suggestion.record)) {
+                result.push(suggestion);
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(fields: &[(&str, &str)]) -> Record {
+        fields.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_matches() {
+        let frp = record(&[("Type", "language"), ("Subtag", "frp"),
+                ("Description", "Arpitan"), ("Description", "Francoprovençal")]);
+        let mut query = Query::new();
+        query.add("Description", "=francoprovençal");
+        assert!(query.matches(&frp));
+        query.add("Description", "provencal");
+        assert!(!query.matches(&frp));
+        query.fold = true;
+        assert!(query.matches(&frp));
+        query.add("Type", "=region");
+        assert!(!query.matches(&frp));
+    }
+
+    #[test]
+    fn test_sort_records() {
+        let a = record(&[("Subtag", "aa"), ("Added", "2005-10-16")]);
+        let b = record(&[("Subtag", "bb"), ("Added", "2009-07-29"), ("Deprecated", "2015-04-17")]);
+        let c = record(&[("Subtag", "cc"), ("Added", "2006-03-08"), ("Deprecated", "2012-08-12")]);
+        let mut records = vec![&b, &a, &c];
+        sort_records(&mut records, SortKey::Subtag, false);
+        assert_eq!(records, vec![&a, &b, &c]);
+        sort_records(&mut records, SortKey::Added, true);
+        assert_eq!(records, vec![&b, &c, &a]);
+        sort_records(&mut records, SortKey::Deprecated, false);
+        assert_eq!(records, vec![&c, &b, &a]);
+        sort_records(&mut records, SortKey::Deprecated, true);
+        assert_eq!(records, vec![&b, &c, &a]);
+        assert_eq!("Added".parse::<SortKey>(), Ok(SortKey::Added));
+        assert!("size".parse::<SortKey>().is_err());
+    }
+
+    #[test]
+    fn test_has_fields() {
+        let frp = record(&[("Type", "language"), ("Subtag", "frp"), ("Added", "2009-07-29")]);
+        let mut query = Query::new();
+        query.add("type", "=language");
+        query.require_field("Added");
+        assert!(query.matches(&frp));
+        query.exclude_field("Preferred-Value");
+        assert!(query.matches(&frp));
+        query.exclude_field("added");
+        assert!(!query.matches(&frp));
+    }
+}
@@ -0,0 +1,186 @@
+/// A well-formed BCP 47 language tag, split into its parts (RFC 5646, section 2.1).
+///
+/// Subtags are kept in the case they were written in, the comparisons are
+/// the job of the caller (tags are case insensitive).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LanguageTag {
+    pub language: String,
+    pub extlangs: Vec<String>,
+    pub script: Option<String>,
+    pub region: Option<String>,
+    pub variants: Vec<String>,
+    pub extensions: Vec<(char, Vec<String>)>,
+    pub private_use: Vec<String>,
+}
+
+fn is_alpha(subtag: &str, min: usize, max: usize) -> bool {
+    (min..=max).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+fn is_digit(subtag: &str, len: usize) -> bool {
+    subtag.len() == len && subtag.chars().all(|c| c.is_ascii_digit())
+}
+
+fn is_alphanum(subtag: &str, min: usize, max: usize) -> bool {
+    (min..=max).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn is_variant(subtag: &str) -> bool {
+    is_alphanum(subtag, 5, 8)
+        || (is_alphanum(subtag, 4, 4) && subtag.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+fn is_singleton(subtag: &str) -> bool {
+    subtag.len() == 1 && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn parse_private_use(subtags: &[&str]) -> Result<Vec<String>, String> {
+    if subtags.is_empty() {
+        return Err("empty private use section".to_string());
+    }
+    let mut result = Vec::new();
+    for subtag in subtags {
+        if !is_alphanum(subtag, 1, 8) {
+            return Err(format!("invalid private use subtag \"{}\"", subtag));
+        }
+        result.push(subtag.to_string());
+    }
+    Ok(result)
+}
+
+impl LanguageTag {
+    /// Parses a tag using the `langtag` / `privateuse` productions of RFC 5646.
+    ///
+    /// Grandfathered tags (`i-klingon`, `zh-min-nan`, ...) are not handled here,
+    /// they have to be looked up in the registry as whole tags.
+    pub fn parse(tag: &str) -> Result<LanguageTag, String> {
+        let subtags: Vec<&str> = tag.split('-').collect();
+        if subtags.iter().any(|subtag| subtag.is_empty()) {
+            return Err(format!("\"{}\" has an empty subtag", tag));
+        }
+        let mut result = LanguageTag::default();
+        if subtags[0].eq_ignore_ascii_case("x") {
+            result.private_use = parse_private_use(&subtags[1..])?;
+            return Ok(result);
+        }
+
+        let mut pos = 0;
+        let language = subtags[pos];
+        // 4ALPHA is well-formed, but reserved for future use: that is for the caller to report.
+        if !is_alpha(language, 2, 8) {
+            return Err(format!("invalid language subtag \"{}\"", language));
+        }
+        result.language = language.to_string();
+        pos += 1;
+
+        if language.len() <= 3 {
+            while pos < subtags.len() && result.extlangs.len() < 3 && is_alpha(subtags[pos], 3, 3) {
+                result.extlangs.push(subtags[pos].to_string());
+                pos += 1;
+            }
+        }
+        if pos < subtags.len() && is_alpha(subtags[pos], 4, 4) {
+            result.script = Some(subtags[pos].to_string());
+            pos += 1;
+        }
+        if pos < subtags.len() && (is_alpha(subtags[pos], 2, 2) || is_digit(subtags[pos], 3)) {
+            result.region = Some(subtags[pos].to_string());
+            pos += 1;
+        }
+        while pos < subtags.len() && is_variant(subtags[pos]) {
+            if result.variants.iter().any(|v| v.eq_ignore_ascii_case(subtags[pos])) {
+                return Err(format!("duplicate variant \"{}\"", subtags[pos]));
+            }
+            result.variants.push(subtags[pos].to_string());
+            pos += 1;
+        }
+        while pos < subtags.len() && is_singleton(subtags[pos]) && !subtags[pos].eq_ignore_ascii_case("x") {
+            let singleton = subtags[pos].to_ascii_lowercase().chars().next().unwrap();
+            if result.extensions.iter().any(|(s, _)| *s == singleton) {
+                return Err(format!("duplicate extension \"{}\"", singleton));
+            }
+            pos += 1;
+            let start = pos;
+            while pos < subtags.len() && is_alphanum(subtags[pos], 2, 8) {
+                pos += 1;
+            }
+            if start == pos {
+                return Err(format!("empty extension \"{}\"", singleton));
+            }
+            let values = subtags[start..pos].iter().map(|s| s.to_string()).collect();
+            result.extensions.push((singleton, values));
+        }
+        if pos < subtags.len() && subtags[pos].eq_ignore_ascii_case("x") {
+            result.private_use = parse_private_use(&subtags[pos + 1..])?;
+            pos = subtags.len();
+        }
+        if pos < subtags.len() {
+            return Err(format!("unexpected subtag \"{}\"", subtags[pos]));
+        }
+        Ok(result)
+    }
+}
+
+impl std::fmt::Display for LanguageTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut parts: Vec<&str> = Vec::new();
+        if !self.language.is_empty() {
+            parts.push(&self.language);
+        }
+        parts.extend(self.extlangs.iter().map(|s| s.as_str()));
+        parts.extend(self.script.as_deref());
+        parts.extend(self.region.as_deref());
+        parts.extend(self.variants.iter().map(|s| s.as_str()));
+        let singletons: Vec<String> = self.extensions.iter().map(|(s, _)| s.to_string()).collect();
+        for ((_, values), singleton) in self.extensions.iter().zip(&singletons) {
+            parts.push(singleton);
+            parts.extend(values.iter().map(|s| s.as_str()));
+        }
+        if !self.private_use.is_empty() {
+            parts.push("x");
+            parts.extend(self.private_use.iter().map(|s| s.as_str()));
+        }
+        write!(f, "{}", parts.join("-"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let tag = LanguageTag::parse("sr-Latn-RS").unwrap();
+        assert_eq!(tag.language, "sr");
+        assert_eq!(tag.script.as_deref(), Some("Latn"));
+        assert_eq!(tag.region.as_deref(), Some("RS"));
+
+        let tag = LanguageTag::parse("zh-yue-HK").unwrap();
+        assert_eq!(tag.extlangs, vec!["yue"]);
+        assert_eq!(tag.region.as_deref(), Some("HK"));
+
+        let tag = LanguageTag::parse("sl-rozaj-biske-1994").unwrap();
+        assert_eq!(tag.variants, vec!["rozaj", "biske", "1994"]);
+
+        let tag = LanguageTag::parse("de-CH-u-co-phonebk-x-private").unwrap();
+        assert_eq!(tag.extensions, vec![('u', vec!["co".to_string(), "phonebk".to_string()])]);
+        assert_eq!(tag.private_use, vec!["private"]);
+        assert_eq!(tag.to_string(), "de-CH-u-co-phonebk-x-private");
+
+        assert_eq!(LanguageTag::parse("es-419").unwrap().region.as_deref(), Some("419"));
+        assert_eq!(LanguageTag::parse("abcd-US").unwrap().language, "abcd");
+        assert_eq!(LanguageTag::parse("x-whatever").unwrap().private_use, vec!["whatever"]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(LanguageTag::parse("").is_err());
+        assert!(LanguageTag::parse("e").is_err());
+        assert!(LanguageTag::parse("abcdefghi").is_err());
+        assert!(LanguageTag::parse("en--US").is_err());
+        assert!(LanguageTag::parse("de-419-DE").is_err());
+        assert!(LanguageTag::parse("en-a-bbb-a-ccc").is_err());
+        assert!(LanguageTag::parse("en-u").is_err());
+        assert!(LanguageTag::parse("sl-rozaj-rozaj").is_err());
+    }
+}
@@ -4,7 +4,7 @@ cargo clean
 cargo build --release
 
 md D:\!\udata
-copy /b/v udata\*                         D:\!\udata
+xcopy /e/i/y udata                         D:\!\udata
 copy /b/v target\release\iana_info.exe    D:\!\ii.exe
 copy /b/v target\release\unicode_info.exe D:\!\ui.exe
 
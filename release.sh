@@ -4,7 +4,7 @@ cargo clean
 cargo build --release
 
 mkdir -p ~/bin/udata
-cp udata/*                     ~/bin/udata
+cp -r udata/*                  ~/bin/udata
 cp target/release/iana_info    ~/bin/ii
 cp target/release/unicode_info ~/bin/ui
 
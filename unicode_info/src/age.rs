@@ -0,0 +1,74 @@
+use crate::color_utils;
+use crate::help;
+use crate::ucd::Ucd;
+
+/// A Unicode version as (major, minor): `15.1`, `15.1.0`, `15` or `V15_1` (PropertyValueAliases.txt).
+pub fn parse_version(text:&str) -> Result<(u32, u32), String> {
+    let version = text.trim().trim_start_matches(['V', 'v']).replace('_', ".");
+    let mut parts = version.split('.').map(|part| part.parse::<u32>());
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(major)), None, _, _) => Ok((major, 0)),
+        (Some(Ok(major)), Some(Ok(minor)), None, _) | (Some(Ok(major)), Some(Ok(minor)), Some(Ok(_)), None) => Ok((major, minor)),
+        _ => Err(format!("invalid Unicode version '{}', expected 15.1", text)),
+    }
+}
+
+/// `--since` / `--age`: the characters assigned in a version or later, and / or in a version or before.
+/// Unassigned code points never match.
+pub struct AgeFilter {
+    since:Option<(u32, u32)>,
+    until:Option<(u32, u32)>,
+}
+
+impl AgeFilter {
+    pub fn new(since:Option<&str>, age:Option<&str>) -> Result<AgeFilter, String> {
+        Ok(AgeFilter {
+            since: since.map(parse_version).transpose()?,
+            until: age.map(parse_version).transpose()?,
+        })
+    }
+
+    /// `age` is the DerivedAge.txt value of the code point.
+    pub fn is_match(&self, age:Option<&str>) -> bool {
+        let Some(Ok(version)) = age.map(parse_version) else {
+            return false;
+        };
+        self.since.is_none_or(|since| version >= since) && self.until.is_none_or(|until| version <= until)
+    }
+}
+
+pub fn get_age(ucd:&Ucd, code_point:u32, flags:&help::Flags) {
+    let age = if !ucd.has_ages() {
+        "(no DerivedAge.txt)"
+    } else {
+        ucd.age(code_point).unwrap_or("Unassigned")
+    };
+    println!("  {} {}", color_utils::label(&"Age (age):".to_string(), flags), age);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_age_filter() {
+        assert_eq!(parse_version("15.1"), Ok((15, 1)));
+        assert_eq!(parse_version("15.1.0"), Ok((15, 1)));
+        assert_eq!(parse_version("13"), Ok((13, 0)));
+        assert_eq!(parse_version("V6_3"), Ok((6, 3)));
+        assert!(parse_version("15.x").is_err());
+        assert!(parse_version("1.2.3.4").is_err());
+
+        let since = AgeFilter::new(Some("13.0"), None).unwrap();
+        assert!(since.is_match(Some("13.0")));
+        assert!(since.is_match(Some("15.1")));
+        assert!(!since.is_match(Some("6.3")));
+        assert!(!since.is_match(None));
+        let range = AgeFilter::new(Some("10.0"), Some("12.1")).unwrap();
+        assert!(range.is_match(Some("11.0")));
+        assert!(!range.is_match(Some("13.0")));
+        // Compared as numbers, not as text
+        assert!(AgeFilter::new(None, Some("9.0")).unwrap().is_match(Some("1.1")));
+        assert!(!AgeFilter::new(None, Some("9.0")).unwrap().is_match(Some("10.0")));
+    }
+}
@@ -1,23 +1,43 @@
 use crate::color_utils;
-use crate::file_utils;
 use crate::help;
-use regex::Regex;
+use crate::ucd::{self, Ucd};
 
-const FILE_BLOCKS:&str = "Blocks.txt";
+pub fn get_block_name(ucd:&Ucd, code_point:u32, flags:&help::Flags) {
+    if let Some(name) = ucd.block(code_point) {
+        println!("  {} {}", color_utils::label(&"Unicode Block:".to_string(), flags), name);
+    }
+}
+
+/// The range of a block: `Box Drawing`, `box_drawing` or `BOX-DRAWING`.
+pub fn find_block(ucd:&Ucd, name:&str) -> Option<(u32, u32)> {
+    let name = ucd::loose_name(name);
+    ucd.all_blocks().find(|(_, _, block)| ucd::loose_name(block) == name).map(|(first, last, _)| (first, last))
+}
 
-pub fn get_block_name(code_point:u32, flags:&help::Flags) {
-    let re_block_info:Regex = Regex::new(r"^([0-9A-F]{4,6})\.\.([0-9A-F]{4,6}); (.+)$").unwrap();
-    let data_path = file_utils::get_data_path(&FILE_BLOCKS.to_string());
-    let lines = file_utils::read_lines(&data_path);
-    for line in lines {
-        let line_content = line.unwrap();
-        for cap in re_block_info.captures_iter(line_content.as_str()) {
-            let start = u32::from_str_radix(&cap[1], 16).unwrap();
-            let end = u32::from_str_radix(&cap[2], 16).unwrap();
-            if code_point >= start && code_point <= end {
-                println!("  {} {}", color_utils::label(&"Unicode Block:".to_string(), flags), &cap[3]);
-                continue;
+/// The number of assigned code points (in UnicodeData.txt) of each block, in the order of the blocks.
+pub fn assigned_counts(ucd:&Ucd) -> Vec<u32> {
+    let blocks:Vec<(u32, u32)> = ucd.all_blocks().map(|(first, last, _)| (first, last)).collect();
+    let mut result = vec![0; blocks.len()];
+    // Both are sorted, an entry only needs to be compared with the blocks that don't end before it.
+    let mut start = 0;
+    for (first, last) in ucd.assigned_ranges() {
+        while start < blocks.len() && blocks[start].1 < first {
+            start += 1;
+        }
+        for (index, (block_first, block_last)) in blocks.iter().enumerate().skip(start) {
+            if *block_first > last {
+                break;
             }
+            result[index] += last.min(*block_last) - first.max(*block_first) + 1;
         }
     }
+    result
+}
+
+/// `--list-blocks`: one line per block, with its range and number of assigned characters.
+pub fn list_blocks(ucd:&Ucd, flags:&help::Flags) {
+    for ((first, last, name), assigned) in ucd.all_blocks().zip(assigned_counts(ucd)) {
+        let range = color_utils::label(&format!("{:<14}", format!("{:04X}..{:04X}", first, last)), flags);
+        println!("{} {}  ({} of {} assigned)", range, name, assigned, last - first + 1);
+    }
 }
@@ -0,0 +1,374 @@
+//! The compiled UCD cache, `udata/ucd.cache`: the tables of `ucd` in one binary file,
+//! memory mapped at startup instead of parsing the text files on every run.
+//!
+//! Layout, all the integers are little endian:
+//!
+//! ```text
+//! "UCDCACHE" version:u32 file_len:u64
+//! source_count:u32, per source: name_len:u32 name size:u64 mtime:u64 hash:u64
+//! table_count:u32,  per table:  range_count:u32 ranges_offset:u32
+//! ranges, per table sorted by code point: first:u32 last:u32 text_offset:u32 text_len:u32
+//! texts (UTF-8)
+//! ```
+
+use std::fs::{self, File};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use memmap2::Mmap;
+
+pub const CACHE_FILE_NAME:&str = "ucd.cache";
+
+const MAGIC:&[u8] = b"UCDCACHE";
+// Increment when the layout or the content of the tables changes.
+const VERSION:u32 = 4;
+// The magic, the version and the length of the file.
+const HEADER_SIZE:usize = 20;
+const RANGE_SIZE:usize = 16;
+
+/// Whether a cache still matches the UCD files.
+#[derive(Debug, PartialEq)]
+pub enum Freshness {
+    Fresh,
+    /// Same content, but some files were touched (checkout, copy...): the cache only needs
+    /// these new stamps, or every run would hash the files again.
+    Touched(Vec<SourceStamp>),
+    Stale,
+}
+
+/// The ranges of code points of one table, and their text.
+pub type Table = Vec<(u32, u32, String)>;
+
+/// What the cache was built from, to know when it has to be rebuilt.
+#[derive(Debug, PartialEq)]
+pub struct SourceStamp {
+    pub name:String,
+    pub size:u64,
+    /// Nanoseconds since 1970.
+    pub mtime:u64,
+    /// FNV-1a of the content, a file touched but not changed doesn't need a rebuild.
+    pub hash:u64,
+}
+
+fn fnv1a(bytes:&[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x100000001b3))
+}
+
+fn mtime(metadata:&fs::Metadata) -> u64 {
+    metadata.modified().ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_nanos() as u64)
+}
+
+impl SourceStamp {
+    /// For an optional file that is not there: any file appearing later has another size.
+    pub fn missing(name:&str) -> SourceStamp {
+        SourceStamp { name: name.to_string(), size: u64::MAX, mtime: 0, hash: 0 }
+    }
+
+    fn is_missing(&self) -> bool {
+        self.size == u64::MAX
+    }
+}
+
+/// Reads a UCD file, and its stamp.
+pub fn read_source(data_dir:&Path, name:&str) -> Result<(String, SourceStamp), String> {
+    let path = data_dir.join(name);
+    let error = |err:std::io::Error| format!("cannot read {}: {}", path.display(), err);
+    let metadata = fs::metadata(&path).map_err(error)?;
+    let text = fs::read_to_string(&path).map_err(error)?;
+    let stamp = SourceStamp {
+        name: name.to_string(),
+        size: metadata.len(),
+        mtime: mtime(&metadata),
+        hash: fnv1a(text.as_bytes()),
+    };
+    Ok((text, stamp))
+}
+
+fn push_u32(bytes:&mut Vec<u8>, value:u32) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+fn push_u64(bytes:&mut Vec<u8>, value:u64) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+/// The content of a cache file. The ranges of each table must be sorted and not overlap.
+pub fn serialize(sources:&[SourceStamp], tables:&[Table]) -> Vec<u8> {
+    let mut result:Vec<u8> = Vec::new();
+    result.extend_from_slice(MAGIC);
+    push_u32(&mut result, VERSION);
+    // Set at the end
+    push_u64(&mut result, 0);
+    push_u32(&mut result, sources.len() as u32);
+    for source in sources {
+        push_u32(&mut result, source.name.len() as u32);
+        result.extend_from_slice(source.name.as_bytes());
+        push_u64(&mut result, source.size);
+        push_u64(&mut result, source.mtime);
+        push_u64(&mut result, source.hash);
+    }
+
+    push_u32(&mut result, tables.len() as u32);
+    let mut ranges_offset = result.len() + tables.len() * 8;
+    for table in tables {
+        push_u32(&mut result, table.len() as u32);
+        push_u32(&mut result, ranges_offset as u32);
+        ranges_offset += table.len() * RANGE_SIZE;
+    }
+    let mut text_offset = ranges_offset;
+    for table in tables {
+        for (first, last, text) in table {
+            push_u32(&mut result, *first);
+            push_u32(&mut result, *last);
+            push_u32(&mut result, text_offset as u32);
+            push_u32(&mut result, text.len() as u32);
+            text_offset += text.len();
+        }
+    }
+    for table in tables {
+        for (_, _, text) in table {
+            result.extend_from_slice(text.as_bytes());
+        }
+    }
+    let file_len = (result.len() as u64).to_le_bytes();
+    result[MAGIC.len() + 4..HEADER_SIZE].copy_from_slice(&file_len);
+    result
+}
+
+/// Writes the cache next to its final name first, so that another process never maps half a file.
+/// The temporary file is per process, two runs rebuilding the cache at the same time don't write
+/// into the same file: the last rename wins, with a complete file either way.
+pub fn write(path:&Path, bytes:&[u8]) -> Result<(), String> {
+    let temp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&temp_path, bytes)
+        .and_then(|_| fs::rename(&temp_path, path))
+        .map_err(|err| format!("cannot write {}: {}", path.display(), err))
+}
+
+enum Bytes {
+    Mapped(Mmap),
+    Owned(Vec<u8>),
+}
+
+/// A cache file, mapped in memory, or just built if it could not be saved.
+pub struct CacheData {
+    bytes:Bytes,
+    // The offset of the table directory, after the variable size list of sources.
+    directory:usize,
+}
+
+impl CacheData {
+    pub fn map(path:&Path) -> Result<CacheData, String> {
+        let file = File::open(path).map_err(|err| format!("cannot open {}: {}", path.display(), err))?;
+        // Safety: the cache is only ever replaced by a rename, never modified in place.
+        let mmap = unsafe { Mmap::map(&file) }.map_err(|err| format!("cannot map {}: {}", path.display(), err))?;
+        CacheData::check(CacheData { bytes: Bytes::Mapped(mmap), directory: 0 })
+    }
+
+    pub fn from_bytes(bytes:Vec<u8>) -> Result<CacheData, String> {
+        CacheData::check(CacheData { bytes: Bytes::Owned(bytes), directory: 0 })
+    }
+
+    fn check(mut cache:CacheData) -> Result<CacheData, String> {
+        let error = || "not a cache file of this version of unicode_info".to_string();
+        if !cache.bytes().starts_with(MAGIC) || cache.u32_at(MAGIC.len()) != Some(VERSION) {
+            return Err(error());
+        }
+        // A truncated file (disk full, copied partially...) would otherwise only miss some texts.
+        if cache.u64_at(MAGIC.len() + 4) != Some(cache.bytes().len() as u64) {
+            return Err("truncated cache file".to_string());
+        }
+        cache.directory = cache.sources_and_end().ok_or_else(error)?.1;
+        Ok(cache)
+    }
+
+    fn bytes(&self) -> &[u8] {
+        match &self.bytes {
+            Bytes::Mapped(mmap) => mmap,
+            Bytes::Owned(bytes) => bytes,
+        }
+    }
+
+    // The reads are checked, a damaged file gives None rather than a panic.
+    fn u32_at(&self, offset:usize) -> Option<u32> {
+        let bytes = self.bytes().get(offset..offset + 4)?;
+        Some(u32::from_le_bytes(bytes.try_into().ok()?))
+    }
+
+    fn u64_at(&self, offset:usize) -> Option<u64> {
+        let bytes = self.bytes().get(offset..offset + 8)?;
+        Some(u64::from_le_bytes(bytes.try_into().ok()?))
+    }
+
+    fn text_at(&self, offset:usize, len:usize) -> Option<&str> {
+        std::str::from_utf8(self.bytes().get(offset..offset + len)?).ok()
+    }
+
+    // The stamps of the sources, and the offset of the table directory following them.
+    fn sources_and_end(&self) -> Option<(Vec<SourceStamp>, usize)> {
+        let mut offset = HEADER_SIZE;
+        let count = self.u32_at(offset)?;
+        offset += 4;
+        let mut result:Vec<SourceStamp> = Vec::new();
+        for _ in 0..count {
+            let name_len = self.u32_at(offset)? as usize;
+            let name = self.text_at(offset + 4, name_len)?.to_string();
+            offset += 4 + name_len;
+            result.push(SourceStamp {
+                name,
+                size: self.u64_at(offset)?,
+                mtime: self.u64_at(offset + 8)?,
+                hash: self.u64_at(offset + 16)?,
+            });
+            offset += 24;
+        }
+        Some((result, offset))
+    }
+
+    pub fn sources(&self) -> Option<Vec<SourceStamp>> {
+        self.sources_and_end().map(|(sources, _)| sources)
+    }
+
+    /// Whether the cache was built from these files, as they are now.
+    /// A file missing now must have been missing when the cache was built.
+    pub fn freshness(&self, data_dir:&Path, names:&[&str]) -> Freshness {
+        let Some(sources) = self.sources() else {
+            return Freshness::Stale;
+        };
+        if !sources.iter().map(|source| source.name.as_str()).eq(names.iter().copied()) {
+            return Freshness::Stale;
+        }
+        let mut touched = false;
+        let mut stamps:Vec<SourceStamp> = Vec::new();
+        for source in sources {
+            let path = data_dir.join(&source.name);
+            match fs::metadata(&path) {
+                Err(_) if source.is_missing() => {}
+                Err(_) => return Freshness::Stale,
+                Ok(metadata) if metadata.len() != source.size => return Freshness::Stale,
+                Ok(metadata) if mtime(&metadata) == source.mtime => {}
+                Ok(metadata) => {
+                    if !fs::read(&path).is_ok_and(|bytes| fnv1a(&bytes) == source.hash) {
+                        return Freshness::Stale;
+                    }
+                    touched = true;
+                    stamps.push(SourceStamp { mtime: mtime(&metadata), ..source });
+                    continue;
+                }
+            }
+            stamps.push(source);
+        }
+        if touched { Freshness::Touched(stamps) } else { Freshness::Fresh }
+    }
+
+    /// A copy of the cache with other stamps for the same files, the tables don't change.
+    pub fn restamped(&self, stamps:&[SourceStamp]) -> Vec<u8> {
+        let mut result = self.bytes().to_vec();
+        let mut offset = HEADER_SIZE + 4;
+        for stamp in stamps {
+            offset += 4 + stamp.name.len();
+            result[offset..offset + 8].copy_from_slice(&stamp.size.to_le_bytes());
+            result[offset + 8..offset + 16].copy_from_slice(&stamp.mtime.to_le_bytes());
+            result[offset + 16..offset + 24].copy_from_slice(&stamp.hash.to_le_bytes());
+            offset += 24;
+        }
+        result
+    }
+
+    /// All the ranges of a table, as (first, last, text).
+    pub fn entries(&self, table:usize) -> impl Iterator<Item = (u32, u32, &str)> {
+        let count = if table < self.u32_at(self.directory).unwrap_or(0) as usize {
+            self.u32_at(self.directory + 4 + table * 8).unwrap_or(0) as usize
+        } else {
+            0
+        };
+        let ranges = self.u32_at(self.directory + 8 + table * 8).unwrap_or(0) as usize;
+        (0..count).filter_map(move |index| {
+            let range = ranges + index * RANGE_SIZE;
+            let text = self.text_at(self.u32_at(range + 8)? as usize, self.u32_at(range + 12)? as usize)?;
+            Some((self.u32_at(range)?, self.u32_at(range + 4)?, text))
+        })
+    }
+
+    /// The text of the range containing the code point, in a table.
+    pub fn lookup(&self, table:usize, code_point:u32) -> Option<&str> {
+        if table >= self.u32_at(self.directory)? as usize {
+            return None;
+        }
+        let entry = self.directory + 4 + table * 8;
+        let count = self.u32_at(entry)? as usize;
+        let ranges = self.u32_at(entry + 4)? as usize;
+
+        // The first range that doesn't end before the code point.
+        let (mut low, mut high) = (0, count);
+        while low < high {
+            let middle = (low + high) / 2;
+            if self.u32_at(ranges + middle * RANGE_SIZE + 4)? < code_point {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        let range = ranges + low * RANGE_SIZE;
+        if low == count || self.u32_at(range)? > code_point {
+            return None;
+        }
+        self.text_at(self.u32_at(range + 8)? as usize, self.u32_at(range + 12)? as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache() {
+        let dir = std::env::temp_dir().join(format!("unicode_info_cache_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("Blocks.txt"), "0000..007F; Basic Latin\n").unwrap();
+        let (_, stamp) = read_source(&dir, "Blocks.txt").unwrap();
+
+        let tables:Vec<Table> = vec![
+            vec![(0x41, 0x41, "A".to_string()), (0x3400, 0x4DBF, "CJK".to_string())],
+            vec![(0x00, 0x7F, "Basic Latin".to_string())],
+        ];
+        let cache = CacheData::from_bytes(serialize(&[stamp], &tables)).unwrap();
+        assert_eq!(cache.lookup(0, 0x41), Some("A"));
+        assert_eq!(cache.lookup(0, 0x4000), Some("CJK"));
+        assert_eq!(cache.lookup(0, 0x42), None);
+        assert_eq!(cache.lookup(0, 0x10FFFF), None);
+        assert_eq!(cache.lookup(1, 0x20), Some("Basic Latin"));
+        assert_eq!(cache.lookup(2, 0x20), None);
+        assert_eq!(cache.entries(0).map(|(first, _, _)| first).collect::<Vec<u32>>(), vec![0x41, 0x3400]);
+        assert_eq!(cache.entries(2).count(), 0);
+
+        assert_eq!(cache.freshness(&dir, &["Blocks.txt"]), Freshness::Fresh);
+        assert_eq!(cache.freshness(&dir, &["Blocks.txt", "NamesList.txt"]), Freshness::Stale);
+        // Deleted since the build
+        assert_eq!(cache.freshness(&dir.join("none"), &["Blocks.txt"]), Freshness::Stale);
+        let missing = CacheData::from_bytes(serialize(&[SourceStamp::missing("Blocks.txt")], &[])).unwrap();
+        assert_eq!(missing.freshness(&dir, &["Blocks.txt"]), Freshness::Stale);
+        assert_eq!(missing.freshness(&dir.join("none"), &["Blocks.txt"]), Freshness::Fresh);
+
+        // Touched, same content
+        let file = File::options().write(true).open(dir.join("Blocks.txt")).unwrap();
+        file.set_modified(UNIX_EPOCH + std::time::Duration::from_secs(1_000_000)).unwrap();
+        let Freshness::Touched(stamps) = cache.freshness(&dir, &["Blocks.txt"]) else {
+            panic!("expected Touched");
+        };
+        let restamped = CacheData::from_bytes(cache.restamped(&stamps)).unwrap();
+        assert_eq!(restamped.freshness(&dir, &["Blocks.txt"]), Freshness::Fresh);
+        assert_eq!(restamped.lookup(1, 0x20), Some("Basic Latin"));
+
+        fs::write(dir.join("Blocks.txt"), "0000..007F; Basic Latin!\n").unwrap();
+        assert_eq!(cache.freshness(&dir, &["Blocks.txt"]), Freshness::Stale);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(CacheData::from_bytes(b"UCDCACHE".to_vec()).is_err());
+        let mut truncated = serialize(&[], &tables);
+        truncated.pop();
+        assert!(CacheData::from_bytes(truncated).is_err());
+    }
+}
@@ -0,0 +1,34 @@
+use crate::color_utils;
+use crate::help;
+use crate::ucd::Ucd;
+
+// The character itself, if it can be shown: not for controls, formats, surrogates, unassigned
+// or line separators, and combining marks are shown on a dotted circle.
+fn glyph(code_point:u32, general_category:&str) -> String {
+    let Some(chr) = char::from_u32(code_point) else {
+        return String::new();
+    };
+    if general_category.starts_with('C') || general_category == "Zl" || general_category == "Zp" {
+        String::new()
+    } else if general_category.starts_with('M') {
+        format!("\u{25CC}{}", chr)
+    } else {
+        chr.to_string()
+    }
+}
+
+/// One line of the compact table: code point, glyph and name.
+pub fn char_line(code_point:u32, name:&str, general_category:&str, flags:&help::Flags) -> String {
+    let label = color_utils::label(&format!("U+{:04X}", code_point), flags);
+    format!("{}\t{}\t{}", label, glyph(code_point, general_category), name)
+}
+
+/// One line per code point of the range kept by `keep` (`--since`, `--prop`...), the unassigned ones included.
+pub fn print_range(ucd:&Ucd, first:u32, last:u32, keep:impl Fn(u32) -> bool, flags:&help::Flags) {
+    for code_point in (first..=last).filter(|code_point| keep(*code_point)) {
+        match ucd.unicode_data(code_point) {
+            Some(fields) if fields.len() > 2 => println!("{}", char_line(code_point, fields[1], fields[2], flags)),
+            _ => println!("{}", char_line(code_point, "<unassigned>", "Cn", flags)),
+        }
+    }
+}
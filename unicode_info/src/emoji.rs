@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use crate::color_utils;
+use crate::help;
+use crate::ucd::Ucd;
+
+/// The emoji-data.txt properties shown for each character.
+const EMOJI_PROPERTIES:&[&str] = &[
+    "Emoji", "Emoji_Presentation", "Emoji_Modifier", "Emoji_Modifier_Base", "Emoji_Component", "Extended_Pictographic",
+];
+
+pub fn get_emoji_properties(ucd:&Ucd, code_point:u32, flags:&help::Flags) {
+    let label = color_utils::label(&"Emoji Properties:".to_string(), flags);
+    let Some(properties) = ucd.emoji_properties(code_point) else {
+        println!("  {} (no emoji-data.txt)", label);
+        return;
+    };
+    let shown:Vec<&str> = EMOJI_PROPERTIES.iter().copied().filter(|property| properties.contains(property)).collect();
+    if shown.is_empty() {
+        println!("  {} -", label);
+    } else {
+        println!("  {} {}", label, shown.join(", "));
+    }
+}
+
+/// An emoji sequence: keycap, flag, modifier, ZWJ... `kind` is its type in the file, `RGI_Emoji_ZWJ_Sequence`.
+#[derive(Debug)]
+pub struct Sequence<'a> {
+    pub code_points:Vec<u32>,
+    pub kind:&'a str,
+    pub name:&'a str,
+}
+
+/// The emoji sequences of more than one code point, to find them in a text.
+pub struct EmojiSequences<'a> {
+    sequences:HashMap<Vec<u32>, Sequence<'a>>,
+    longest:usize,
+}
+
+impl<'a> EmojiSequences<'a> {
+    pub fn new(ucd:&'a Ucd) -> EmojiSequences<'a> {
+        let sequences:HashMap<Vec<u32>, Sequence> = ucd.emoji_sequences()
+            .filter(|(code_points, _, _)| code_points.len() > 1)
+            .map(|(code_points, kind, name)| (code_points.clone(), Sequence { code_points, kind, name }))
+            .collect();
+        let longest = sequences.keys().map(Vec::len).max().unwrap_or(0);
+        EmojiSequences { sequences, longest }
+    }
+
+    /// The longest sequence the code points start with.
+    pub fn longest_at(&self, code_points:&[u32]) -> Option<&Sequence<'a>> {
+        (2..=self.longest.min(code_points.len())).rev().find_map(|length| self.sequences.get(&code_points[..length]))
+    }
+}
+
+pub fn print_sequence(sequence:&Sequence, flags:&help::Flags) {
+    let text:String = sequence.code_points.iter().filter_map(|code_point| char::from_u32(*code_point)).collect();
+    let code_points:Vec<String> = sequence.code_points.iter().map(|code_point| format!("{:04X}", code_point)).collect();
+    println!("===== {} ==========", text);
+    // The names use \x{23} for the characters that can't be in the file, like # (a comment).
+    println!("  {} {}", color_utils::label(&"Emoji Sequence:".to_string(), flags), sequence.name.replace("\\x{23}", "#"));
+    println!("  {} {}", color_utils::label(&"Type          :".to_string(), flags), sequence.kind);
+    println!("  {} {}", color_utils::label(&"Code Points   :".to_string(), flags), code_points.join(" "));
+    println!("----------------------");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emoji_sequences() {
+        let ucd = Ucd::from_texts(&[
+            ("emoji-data.txt", "1F468 ; Emoji # E0.6 [1] (👨) man\n1F468 ; Emoji_Presentation # E0.6 [1] (👨) man\n\
+                    1F468 ; Emoji_Modifier_Base # E0.6 [1] (👨) man\n1F468 ; Extended_Pictographic # E0.6 [1] (👨) man\n"),
+            ("emoji-sequences.txt", "1F468 1F3FB ; RGI_Emoji_Modifier_Sequence ; man: light skin tone # E1.0 [1]\n"),
+            ("emoji-zwj-sequences.txt", "1F468 200D 1F469 200D 1F467 ; RGI_Emoji_ZWJ_Sequence ; family: man, woman, girl # E2.0 [1]\n\
+                    1F468 200D 1F469 200D 1F467 200D 1F466 ; RGI_Emoji_ZWJ_Sequence ; family: man, woman, girl, boy # E2.0 [1]\n"),
+        ]);
+
+        assert_eq!(ucd.emoji_properties(0x1F468), Some(vec!["Emoji", "Emoji_Modifier_Base", "Emoji_Presentation", "Extended_Pictographic"]));
+        assert_eq!(ucd.emoji_properties(0x41), Some(vec![]));
+        let sequences = EmojiSequences::new(&ucd);
+        let family = [0x1F468, 0x200D, 0x1F469, 0x200D, 0x1F467, 0x41];
+        assert_eq!(sequences.longest_at(&family).map(|sequence| sequence.name), Some("family: man, woman, girl"));
+        let family = [0x1F468, 0x200D, 0x1F469, 0x200D, 0x1F467, 0x200D, 0x1F466];
+        assert_eq!(sequences.longest_at(&family).map(|sequence| sequence.name), Some("family: man, woman, girl, boy"));
+        assert_eq!(sequences.longest_at(&[0x1F468, 0x1F3FB]).map(|sequence| sequence.kind), Some("RGI_Emoji_Modifier_Sequence"));
+        assert!(sequences.longest_at(&[0x1F468, 0x200D]).is_none());
+        assert!(sequences.longest_at(&[0x1F468]).is_none());
+    }
+}
@@ -1,18 +1,10 @@
 use std::env;
-use std::fs::File;
-use std::io::{self, BufRead, BufReader};
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 
-pub fn read_lines(data_path:&Path) -> io::Lines<BufReader<File>> {
-    let file = File::open(data_path).unwrap(); 
-    return io::BufReader::new(file).lines(); 
-}
-
-pub fn get_data_path(file_name:&String) -> PathBuf {
+pub fn get_data_dir() -> PathBuf {
     let result = env::current_exe().unwrap()
         .parent().unwrap()
-        .join("udata")
-        .join(file_name);
+        .join("udata");
     // print!("{:?}", &result);
     return result;
 }
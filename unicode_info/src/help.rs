@@ -1,4 +1,5 @@
 use atty::Stream;
+use crate::input::{Input, InputMode};
 use std::process::exit;
 
 #[derive(Debug)]
@@ -24,9 +25,21 @@ pub struct Flags {
 		pub show_unicode_data_slc:bool,
 		pub show_unicode_data_stc:bool,
 	pub show_block_info:bool,
+	pub show_script_info:bool,
+	pub show_age:bool,
+	pub show_properties:bool,
+	pub show_east_asian_width:bool,
+	pub show_emoji:bool,
 	pub show_name_info:bool,
     pub show_color:bool,
-	pub rest: Vec<String>,
+	pub name_regex:Option<String>,
+	pub list_blocks:bool,
+	pub since:Option<String>,
+	pub age:Option<String>,
+	pub properties:Vec<String>,
+	pub ambiguous_wide:bool,
+	pub input_mode:InputMode,
+	pub inputs: Vec<Input>,
 }
 
 impl Flags {
@@ -53,15 +66,38 @@ impl Flags {
 				show_unicode_data_slc: show_default,
 				show_unicode_data_stc: show_default,
 			show_block_info: show_default,
+			show_script_info: show_default,
+			show_age: show_default,
+			show_properties: show_default,
+			show_east_asian_width: show_default,
+			show_emoji: show_default,
 			show_name_info: show_default,
 			show_color: atty::is(Stream::Stdout),
-			rest: Vec::new(),
+			name_regex: None,
+			list_blocks: false,
+			since: None,
+			age: None,
+			properties: Vec::new(),
+			ambiguous_wide: false,
+			input_mode: InputMode::Auto,
+			inputs: Vec::new(),
 		}
 	}
+
+	/// The plain arguments (not --utf8 / --utf16), for the commands.
+	pub fn args(&self) -> Vec<&str> {
+		self.inputs.iter().filter_map(|input| match input {
+			Input::Arg(arg) => Some(arg.as_str()),
+			_ => None,
+		}).collect()
+	}
 }
 
 pub fn print_help() {
-    println!("Usage: unicode_info [<flag> ...] <string_or_hex_code_point> ...");
+    println!("Usage: unicode_info [<flag> ...] <string_or_code_point> ...");
+    println!("       unicode_info find <text> [--name-regex <regex>]");
+    println!("       unicode_info width <text> [--ambiguous-wide]");
+    println!("       unicode_info build-cache");
     println!("");
     println!("Find and show Unicode info (from the standard Unicode data files)");
     println!("");
@@ -86,10 +122,40 @@ pub fn print_help() {
     println!("    -udslc   : show Unicode Data - Simple Lowercase Mapping");
     println!("    -udstc   : show Unicode Data - Simple Titlecase Mapping");
 	println!("  -b        : show Unicode block info (from Blocks.txt)");
+	println!("  -sc       : show Script and Script_Extensions (from Scripts.txt, ScriptExtensions.txt)");
+	println!("  -age      : show the Unicode version the character was added in (from DerivedAge.txt)");
+	println!("  -p        : show the binary properties (from PropList.txt, DerivedCoreProperties.txt)");
+	println!("  -ea       : show the East Asian Width (from EastAsianWidth.txt)");
+	println!("  -e        : show the emoji properties, and name the emoji sequences of the text");
+	println!("              (from emoji-data.txt, emoji-sequences.txt, emoji-zwj-sequences.txt)");
 	println!("  -n        : show name info (from NamesList.txt)");
     println!("--color=always   : force to always use colors");
     println!("--color=never    : force to never use colors");
     println!("where the value can be a substring or exact match if it starts with '='");
+    println!();
+    println!("Code points can be written as U+1F4A9, 0x1F4A9, 1F4A9 (4 to 6 hex digits), d128169 (decimal),");
+    println!("\\u{{1F4A9}}, \\U0001F4A9, \\ud83d\\udca9 (surrogate pairs), &#x1F4A9;, &#128169; or &hearts;");
+    println!("anything else is the text itself.");
+    println!("A range, 0041..005A or U+2500..U+257F, is shown as a table with one line per code point.");
+    println!("--block <name>   : show the code points of a block as a table, \"Box Drawing\"");
+    println!("--list-blocks    : list the blocks, with their range and number of assigned characters");
+    println!("--utf8 <bytes>   : a UTF-8 byte sequence, \"F0 9F 92 A9\"");
+    println!("--utf16 <units>  : a UTF-16 sequence, \"D83D DCA9\"");
+    println!("--text           : read the arguments as text, CAFE is C, A, F, E (not U+CAFE)");
+    println!("                   (and find, width or build-cache are not commands)");
+    println!("--cp             : read the arguments as code points, 41 is U+0041 (not 4, 1)");
+    println!("--since <version>: only the characters added in Unicode <version> or later, \"13.0\"");
+    println!("--age <version>  : only the characters that exist in Unicode <version>, added in it or before");
+    println!("--prop <property>: only the characters with the binary property, \"XID_Start\", can be repeated");
+    println!("                   (these work on ranges, blocks and text, and can be combined)");
+    println!();
+    println!("find <text>      : list the characters with <text> in their name, alias or annotation");
+    println!("--name-regex <re>: list the characters with a name, alias or annotation matching the regex");
+    println!("                   (case insensitive, can be used without find)");
+    println!("width <text>     : the columns the text takes in a terminal, and those of each character");
+    println!("--ambiguous-wide : count the ambiguous width (ea=A) characters as 2 columns, as in a CJK locale");
+    println!("build-cache      : compile the data files into udata/ucd.cache");
+    println!("                   (also done automatically when a data file changes)");
     exit(1);
 }
 
@@ -98,10 +164,17 @@ pub fn parse_args(args: &Vec<String>) -> Flags {
 
 	let mut show_all = true;
 	let mut show_color = atty::is(Stream::Stdout);
-	let mut rest: Vec<String> = Vec::new();
+	let mut inputs: Vec<Input> = Vec::new();
+	let mut name_regex: Option<String> = None;
+	let mut input_mode = InputMode::Auto;
+	let mut list_blocks = false;
+	let mut since: Option<String> = None;
+	let mut age: Option<String> = None;
+	let mut properties: Vec<String> = Vec::new();
+	let mut ambiguous_wide = false;
 
-	for i in 1..args.len() {
-		let arg = &args[i];
+	let mut arg_iter = args.iter().skip(1);
+	while let Some(arg) = arg_iter.next() {
         match arg.as_str() {
 			"-u"             => { show_all = false; result.show_utf = true },
 				"-d"             => { show_all = false; result.show_dec = true },
@@ -124,11 +197,48 @@ pub fn parse_args(args: &Vec<String>) -> Flags {
 				"-udslc"         => { show_all = false; result.show_unicode_data_slc = true },
 				"-udstc"         => { show_all = false; result.show_unicode_data_stc = true },
 			"-b"             => { show_all = false; result.show_block_info = true },
+			"-sc"            => { show_all = false; result.show_script_info = true },
+			"-age"           => { show_all = false; result.show_age = true },
+			"-p"             => { show_all = false; result.show_properties = true },
+			"-ea"            => { show_all = false; result.show_east_asian_width = true },
+			"-e"             => { show_all = false; result.show_emoji = true },
 			"-n"             => { show_all = false; result.show_name_info = true },
 			"--color=always" => { show_color = true },
 			"--color=never"  => { show_color = false },
+			"--name-regex"   => match arg_iter.next() {
+				Some(value) => name_regex = Some(value.to_string()),
+				None        => print_help(),
+			},
+			"--utf8"         => match arg_iter.next() {
+				Some(value) => inputs.push(Input::Utf8(value.to_string())),
+				None        => print_help(),
+			},
+			"--utf16"        => match arg_iter.next() {
+				Some(value) => inputs.push(Input::Utf16(value.to_string())),
+				None        => print_help(),
+			},
+			"--block"        => match arg_iter.next() {
+				Some(value) => inputs.push(Input::Block(value.to_string())),
+				None        => print_help(),
+			},
+			"--since"        => match arg_iter.next() {
+				Some(value) => since = Some(value.to_string()),
+				None        => print_help(),
+			},
+			"--age"          => match arg_iter.next() {
+				Some(value) => age = Some(value.to_string()),
+				None        => print_help(),
+			},
+			"--prop"         => match arg_iter.next() {
+				Some(value) => properties.push(value.to_string()),
+				None        => print_help(),
+			},
+			"--ambiguous-wide" => { ambiguous_wide = true },
+			"--list-blocks"  => { list_blocks = true },
+			"--text"         => { input_mode = InputMode::Text },
+			"--cp"           => { input_mode = InputMode::CodePoint },
             "-h" | "--help"  => print_help(),
-            _                => rest.push(arg.to_string())
+            _                => inputs.push(Input::Arg(arg.to_string()))
         };
     }
 	if show_all {
@@ -170,7 +280,14 @@ pub fn parse_args(args: &Vec<String>) -> Flags {
 		}
 	}
 	result.show_color = show_color;
-	result.rest = rest;
+	result.name_regex = name_regex;
+	result.input_mode = input_mode;
+	result.list_blocks = list_blocks;
+	result.since = since;
+	result.age = age;
+	result.properties = properties;
+	result.ambiguous_wide = ambiguous_wide;
+	result.inputs = inputs;
     // dbg!(&result);
     return result;
 }
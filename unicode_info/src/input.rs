@@ -0,0 +1,296 @@
+/// How to read the plain arguments.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputMode {
+    /// A code point notation if the argument is one, else the text itself.
+    Auto,
+    /// Always the text itself (`--text`): `CAFE` is C, A, F, E.
+    Text,
+    /// Always code points (`--cp`), also 1 to 3 hex digits: `41` is U+0041.
+    CodePoint,
+}
+
+/// One command line argument to show the characters of, in the order given.
+#[derive(Debug, PartialEq)]
+pub enum Input {
+    Arg(String),
+    /// `--utf8 "F0 9F 92 A9"`
+    Utf8(String),
+    /// `--utf16 "D83D DCA9"`
+    Utf16(String),
+    /// `--block "Box Drawing"`
+    Block(String),
+}
+
+/// What an input selects: characters to show in full, or a range to show as a table.
+#[derive(Debug, PartialEq)]
+pub enum Selection {
+    CodePoints(Vec<u32>),
+    Range(u32, u32),
+}
+
+// Only hex digits (from_str_radix would also take a sign), with a length in the range.
+fn parse_hex(text:&str, min_len:usize, max_len:usize) -> Option<u32> {
+    if text.len() < min_len || text.len() > max_len || !text.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(text, 16).ok()
+}
+
+fn parse_decimal(text:&str) -> Option<u32> {
+    if text.is_empty() || text.len() > 7 || !text.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+fn is_high_surrogate(unit:u32) -> bool {
+    (0xD800..=0xDBFF).contains(&unit)
+}
+
+fn is_low_surrogate(unit:u32) -> bool {
+    (0xDC00..=0xDFFF).contains(&unit)
+}
+
+// A high surrogate followed by a low one is one code point, a lone surrogate is kept as is.
+fn combine_surrogates(units:&[u32]) -> Vec<u32> {
+    let mut result:Vec<u32> = Vec::new();
+    let mut index = 0;
+    while index < units.len() {
+        let unit = units[index];
+        match units.get(index + 1) {
+            Some(&low) if is_high_surrogate(unit) && is_low_surrogate(low) => {
+                result.push(0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00));
+                index += 2;
+            }
+            _ => {
+                result.push(unit);
+                index += 1;
+            }
+        }
+    }
+    result
+}
+
+// An argument made only of escapes: \u{1F4A9}, \U0001F4A9, \ud83d\udca9, &#x1F4A9;, &#128169;, &hearts;
+fn parse_escapes(text:&str) -> Option<Vec<u32>> {
+    let mut units:Vec<u32> = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let (unit, tail) = if let Some(after) = rest.strip_prefix("\\u{") {
+            let (hex, tail) = after.split_once('}')?;
+            (parse_hex(hex, 1, 6)?, tail)
+        } else if let Some(after) = rest.strip_prefix("\\u") {
+            (parse_hex(after.get(..4)?, 4, 4)?, &after[4..])
+        } else if let Some(after) = rest.strip_prefix("\\U") {
+            (parse_hex(after.get(..8)?, 8, 8)?, &after[8..])
+        } else if let Some(after) = rest.strip_prefix("&#x").or_else(|| rest.strip_prefix("&#X")) {
+            let (hex, tail) = after.split_once(';')?;
+            (parse_hex(hex, 1, 6)?, tail)
+        } else if let Some(after) = rest.strip_prefix("&#") {
+            let (decimal, tail) = after.split_once(';')?;
+            (parse_decimal(decimal)?, tail)
+        } else if let Some(after) = rest.strip_prefix('&') {
+            let (name, tail) = after.split_once(';')?;
+            (html_entity(name)?, tail)
+        } else {
+            return None;
+        };
+        units.push(unit);
+        rest = tail;
+    }
+    Some(combine_surrogates(&units))
+}
+
+/// The code points written in one of the notations: `U+1F4A9`, `0x1F4A9`, `d128169`,
+/// the escapes of `parse_escapes`, or bare hex digits (4 to 6, or 1 to 6 if `any_hex_len`).
+pub fn parse_code_points(text:&str, any_hex_len:bool) -> Option<Vec<u32>> {
+    if let Some(hex) = text.strip_prefix("U+").or_else(|| text.strip_prefix("u+")) {
+        return parse_hex(hex, 1, 6).map(|code_point| vec![code_point]);
+    }
+    if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        return parse_hex(hex, 1, 6).map(|code_point| vec![code_point]);
+    }
+    if let Some(code_point) = text.strip_prefix('d').and_then(parse_decimal) {
+        return Some(vec![code_point]);
+    }
+    if text.starts_with('\\') || text.starts_with('&') {
+        return parse_escapes(text);
+    }
+    parse_hex(text, if any_hex_len { 1 } else { 4 }, 6).map(|code_point| vec![code_point])
+}
+
+// The bytes or code units of --utf8 / --utf16: separated by spaces or commas, or all together.
+fn parse_hex_groups(text:&str, digits:usize) -> Option<Vec<u32>> {
+    let mut result:Vec<u32> = Vec::new();
+    for group in text.split(|chr:char| chr.is_whitespace() || chr == ',').filter(|group| !group.is_empty()) {
+        if group.len() <= digits {
+            result.push(parse_hex(group, 1, digits)?);
+        } else if group.len() % digits == 0 && group.is_ascii() {
+            for start in (0..group.len()).step_by(digits) {
+                result.push(parse_hex(&group[start..start + digits], digits, digits)?);
+            }
+        } else {
+            return None;
+        }
+    }
+    if result.is_empty() { None } else { Some(result) }
+}
+
+pub fn parse_utf8(text:&str) -> Result<Vec<u32>, String> {
+    let bytes:Vec<u8> = parse_hex_groups(text, 2)
+        .ok_or_else(|| format!("expected hex bytes for --utf8, like \"F0 9F 92 A9\", not \"{}\"", text))?
+        .iter().map(|byte| *byte as u8).collect();
+    let decoded = std::str::from_utf8(&bytes).map_err(|err| format!("invalid UTF-8 \"{}\": {}", text, err))?;
+    Ok(decoded.chars().map(|chr| chr as u32).collect())
+}
+
+/// Lone surrogates are kept, to be shown as invalid code points.
+pub fn parse_utf16(text:&str) -> Result<Vec<u32>, String> {
+    let units = parse_hex_groups(text, 4)
+        .ok_or_else(|| format!("expected hex code units for --utf16, like \"D83D DCA9\", not \"{}\"", text))?;
+    Ok(combine_surrogates(&units))
+}
+
+// `0041..005A`, `U+2500..U+257F`: two single code points, in any notation.
+fn parse_range(text:&str, any_hex_len:bool) -> Option<Result<(u32, u32), String>> {
+    let (first, last) = text.split_once("..")?;
+    let single = |side:&str| match parse_code_points(side, any_hex_len)?[..] {
+        [code_point] => Some(code_point),
+        _ => None,
+    };
+    let (first, last) = (single(first)?, single(last)?);
+    if first > last || last > 0x10FFFF {
+        return Some(Err(format!("invalid range \"{}\"", text)));
+    }
+    Some(Ok((first, last)))
+}
+
+/// What one input selects, `find_block` gives the range of a block name.
+pub fn parse_input(input:&Input, mode:InputMode, find_block:impl Fn(&str) -> Option<(u32, u32)>)
+        -> Result<Selection, String> {
+    let text = match input {
+        Input::Utf8(text) => return parse_utf8(text).map(Selection::CodePoints),
+        Input::Utf16(text) => return parse_utf16(text).map(Selection::CodePoints),
+        Input::Block(name) => return find_block(name)
+            .map(|(first, last)| Selection::Range(first, last))
+            .ok_or_else(|| format!("unknown block \"{}\", see --list-blocks", name)),
+        Input::Arg(text) => text,
+    };
+    if mode != InputMode::Text {
+        if let Some(range) = parse_range(text, mode == InputMode::CodePoint) {
+            return range.map(|(first, last)| Selection::Range(first, last));
+        }
+    }
+    let code_points = match mode {
+        InputMode::Text => text.chars().map(|chr| chr as u32).collect(),
+        InputMode::CodePoint => parse_code_points(text, true)
+            .ok_or_else(|| format!("\"{}\" is not a code point (U+1F4A9, 0x1F4A9, 1F4A9, d128169, ...)", text))?,
+        InputMode::Auto => parse_code_points(text, false).unwrap_or_else(|| text.chars().map(|chr| chr as u32).collect()),
+    };
+    Ok(Selection::CodePoints(code_points))
+}
+
+// The 253 named character references of HTML 4 and XHTML (&apos;).
+const HTML_ENTITIES:&[(&str, u32)] = &[
+    ("quot", 34), ("amp", 38), ("apos", 39), ("lt", 60), ("gt", 62),
+    ("nbsp", 160), ("iexcl", 161), ("cent", 162), ("pound", 163), ("curren", 164), ("yen", 165),
+    ("brvbar", 166), ("sect", 167), ("uml", 168), ("copy", 169), ("ordf", 170), ("laquo", 171),
+    ("not", 172), ("shy", 173), ("reg", 174), ("macr", 175), ("deg", 176), ("plusmn", 177),
+    ("sup2", 178), ("sup3", 179), ("acute", 180), ("micro", 181), ("para", 182), ("middot", 183),
+    ("cedil", 184), ("sup1", 185), ("ordm", 186), ("raquo", 187), ("frac14", 188), ("frac12", 189),
+    ("frac34", 190), ("iquest", 191), ("Agrave", 192), ("Aacute", 193), ("Acirc", 194), ("Atilde", 195),
+    ("Auml", 196), ("Aring", 197), ("AElig", 198), ("Ccedil", 199), ("Egrave", 200), ("Eacute", 201),
+    ("Ecirc", 202), ("Euml", 203), ("Igrave", 204), ("Iacute", 205), ("Icirc", 206), ("Iuml", 207),
+    ("ETH", 208), ("Ntilde", 209), ("Ograve", 210), ("Oacute", 211), ("Ocirc", 212), ("Otilde", 213),
+    ("Ouml", 214), ("times", 215), ("Oslash", 216), ("Ugrave", 217), ("Uacute", 218), ("Ucirc", 219),
+    ("Uuml", 220), ("Yacute", 221), ("THORN", 222), ("szlig", 223), ("agrave", 224), ("aacute", 225),
+    ("acirc", 226), ("atilde", 227), ("auml", 228), ("aring", 229), ("aelig", 230), ("ccedil", 231),
+    ("egrave", 232), ("eacute", 233), ("ecirc", 234), ("euml", 235), ("igrave", 236), ("iacute", 237),
+    ("icirc", 238), ("iuml", 239), ("eth", 240), ("ntilde", 241), ("ograve", 242), ("oacute", 243),
+    ("ocirc", 244), ("otilde", 245), ("ouml", 246), ("divide", 247), ("oslash", 248), ("ugrave", 249),
+    ("uacute", 250), ("ucirc", 251), ("uuml", 252), ("yacute", 253), ("thorn", 254), ("yuml", 255),
+    ("OElig", 338), ("oelig", 339), ("Scaron", 352), ("scaron", 353), ("Yuml", 376), ("fnof", 402),
+    ("circ", 710), ("tilde", 732),
+    ("Alpha", 913), ("Beta", 914), ("Gamma", 915), ("Delta", 916), ("Epsilon", 917), ("Zeta", 918),
+    ("Eta", 919), ("Theta", 920), ("Iota", 921), ("Kappa", 922), ("Lambda", 923), ("Mu", 924),
+    ("Nu", 925), ("Xi", 926), ("Omicron", 927), ("Pi", 928), ("Rho", 929), ("Sigma", 931),
+    ("Tau", 932), ("Upsilon", 933), ("Phi", 934), ("Chi", 935), ("Psi", 936), ("Omega", 937),
+    ("alpha", 945), ("beta", 946), ("gamma", 947), ("delta", 948), ("epsilon", 949), ("zeta", 950),
+    ("eta", 951), ("theta", 952), ("iota", 953), ("kappa", 954), ("lambda", 955), ("mu", 956),
+    ("nu", 957), ("xi", 958), ("omicron", 959), ("pi", 960), ("rho", 961), ("sigmaf", 962),
+    ("sigma", 963), ("tau", 964), ("upsilon", 965), ("phi", 966), ("chi", 967), ("psi", 968),
+    ("omega", 969), ("thetasym", 977), ("upsih", 978), ("piv", 982),
+    ("ensp", 8194), ("emsp", 8195), ("thinsp", 8201), ("zwnj", 8204), ("zwj", 8205), ("lrm", 8206),
+    ("rlm", 8207), ("ndash", 8211), ("mdash", 8212), ("lsquo", 8216), ("rsquo", 8217), ("sbquo", 8218),
+    ("ldquo", 8220), ("rdquo", 8221), ("bdquo", 8222), ("dagger", 8224), ("Dagger", 8225), ("bull", 8226),
+    ("hellip", 8230), ("permil", 8240), ("prime", 8242), ("Prime", 8243), ("lsaquo", 8249), ("rsaquo", 8250),
+    ("oline", 8254), ("frasl", 8260), ("euro", 8364), ("image", 8465), ("weierp", 8472), ("real", 8476),
+    ("trade", 8482), ("alefsym", 8501), ("larr", 8592), ("uarr", 8593), ("rarr", 8594), ("darr", 8595),
+    ("harr", 8596), ("crarr", 8629), ("lArr", 8656), ("uArr", 8657), ("rArr", 8658), ("dArr", 8659),
+    ("hArr", 8660), ("forall", 8704), ("part", 8706), ("exist", 8707), ("empty", 8709), ("nabla", 8711),
+    ("isin", 8712), ("notin", 8713), ("ni", 8715), ("prod", 8719), ("sum", 8721), ("minus", 8722),
+    ("lowast", 8727), ("radic", 8730), ("prop", 8733), ("infin", 8734), ("ang", 8736), ("and", 8743),
+    ("or", 8744), ("cap", 8745), ("cup", 8746), ("int", 8747), ("there4", 8756), ("sim", 8764),
+    ("cong", 8773), ("asymp", 8776), ("ne", 8800), ("equiv", 8801), ("le", 8804), ("ge", 8805),
+    ("sub", 8834), ("sup", 8835), ("nsub", 8836), ("sube", 8838), ("supe", 8839), ("oplus", 8853),
+    ("otimes", 8855), ("perp", 8869), ("sdot", 8901), ("lceil", 8968), ("rceil", 8969), ("lfloor", 8970),
+    ("rfloor", 8971), ("lang", 9001), ("rang", 9002), ("loz", 9674), ("spades", 9824), ("clubs", 9827),
+    ("hearts", 9829), ("diams", 9830),
+];
+
+// Case sensitive, &Agrave; and &agrave; are different.
+fn html_entity(name:&str) -> Option<u32> {
+    HTML_ENTITIES.iter().find(|(entity, _)| *entity == name).map(|(_, code_point)| *code_point)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input:Input, mode:InputMode) -> Result<Selection, String> {
+        parse_input(&input, mode, |name| (name == "Basic Latin").then_some((0x00, 0x7F)))
+    }
+
+    fn auto(text:&str) -> Vec<u32> {
+        match parse(Input::Arg(text.to_string()), InputMode::Auto).unwrap() {
+            Selection::CodePoints(code_points) => code_points,
+            Selection::Range(first, last) => panic!("unexpected range {:X}..{:X}", first, last),
+        }
+    }
+
+    #[test]
+    fn test_parse_input() {
+        for text in ["U+1F4A9", "u+1f4a9", "0x1F4A9", "1F4A9", "d128169", "\\u{1F4A9}", "\\U0001F4A9",
+                "\\ud83d\\udca9", "&#x1F4A9;", "&#128169;"] {
+            assert_eq!(auto(text), vec![0x1F4A9], "{}", text);
+        }
+        assert_eq!(auto("&hearts;&amp;"), vec![0x2665, 0x26]);
+        assert_eq!(auto("CAFE"), vec![0xCAFE]);
+        assert_eq!(auto("cafe au lait").len(), 12);
+        assert_eq!(auto("41"), vec![0x34, 0x31]);
+        assert_eq!(auto("&unknown;"), vec![0x26, 0x75, 0x6E, 0x6B, 0x6E, 0x6F, 0x77, 0x6E, 0x3B]);
+        assert_eq!(auto("\\ud83d"), vec![0xD83D]);
+
+        let arg = |text:&str| Input::Arg(text.to_string());
+        assert_eq!(parse(arg("CAFE"), InputMode::Text).unwrap(), Selection::CodePoints(vec![0x43, 0x41, 0x46, 0x45]));
+        assert_eq!(parse(arg("41"), InputMode::CodePoint).unwrap(), Selection::CodePoints(vec![0x41]));
+        assert!(parse(arg("xyz"), InputMode::CodePoint).is_err());
+
+        assert_eq!(parse(arg("0041..005A"), InputMode::Auto).unwrap(), Selection::Range(0x41, 0x5A));
+        assert_eq!(parse(arg("U+2500..U+257F"), InputMode::Auto).unwrap(), Selection::Range(0x2500, 0x257F));
+        assert!(parse(arg("005A..0041"), InputMode::Auto).is_err());
+        assert_eq!(auto("a..f"), vec![0x61, 0x2E, 0x2E, 0x66]);
+        assert_eq!(parse(arg("a..f"), InputMode::CodePoint).unwrap(), Selection::Range(0x0A, 0x0F));
+        assert_eq!(parse(Input::Block("Basic Latin".to_string()), InputMode::Auto).unwrap(), Selection::Range(0, 0x7F));
+        assert!(parse(Input::Block("Klingon".to_string()), InputMode::Auto).is_err());
+
+        assert_eq!(parse_utf8("F0 9F 92 A9").unwrap(), vec![0x1F4A9]);
+        assert_eq!(parse_utf8("F09F92A9 41").unwrap(), vec![0x1F4A9, 0x41]);
+        assert_eq!(parse_utf8("C3,A9").unwrap(), vec![0xE9]);
+        assert!(parse_utf8("F0 9F").is_err());
+        assert!(parse_utf8("hello").is_err());
+        assert_eq!(parse_utf16("D83D DCA9").unwrap(), vec![0x1F4A9]);
+        assert_eq!(parse_utf16("D83DDCA90041").unwrap(), vec![0x1F4A9, 0x41]);
+        assert_eq!(parse_utf16("DCA9").unwrap(), vec![0xDCA9]);
+    }
+}
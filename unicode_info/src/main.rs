@@ -1,16 +1,33 @@
+mod age;
 mod block_name;
+mod cache;
+mod char_table;
 mod color_utils;
+mod emoji;
 mod file_utils;
 mod help;
+mod input;
 mod name_list;
+mod name_search;
+mod properties;
+mod script_info;
+mod ucd;
 mod unicode_data;
 mod utf_info;
+mod width;
 
 use std::env;
+use std::process::exit;
+use age::AgeFilter;
+use emoji::EmojiSequences;
 use help::Flags;
-use regex::Regex;
+use input::{InputMode, Selection};
+use name_search::NameQuery;
+use properties::PropertyFilter;
+use regex::RegexBuilder;
+use ucd::Ucd;
 
-fn get_char_info(code_point:u32, flags:&Flags) {
+fn get_char_info(ucd:&Ucd, code_point:u32, flags:&Flags) {
     let chr = char::from_u32(code_point);
     if chr == None {
         println!("===== Invalid code point: {:04X}h ==========", code_point);
@@ -21,36 +38,190 @@ fn get_char_info(code_point:u32, flags:&Flags) {
             println!("----------------------");
         }
         if flags.show_unicode_data {
-            unicode_data::get_unicode_data(code_point, flags);
+            unicode_data::get_unicode_data(ucd, code_point, flags);
             println!("----------------------");
         }
         if flags.show_block_info {
-            block_name::get_block_name(code_point, flags);
+            block_name::get_block_name(ucd, code_point, flags);
+            println!("----------------------");
+        }
+        if flags.show_script_info {
+            script_info::get_script_info(ucd, code_point, flags);
+            println!("----------------------");
+        }
+        if flags.show_age {
+            age::get_age(ucd, code_point, flags);
+            println!("----------------------");
+        }
+        if flags.show_properties {
+            properties::get_binary_properties(ucd, code_point, flags);
+            println!("----------------------");
+        }
+        if flags.show_east_asian_width {
+            width::get_east_asian_width(ucd, code_point, flags);
+            println!("----------------------");
+        }
+        if flags.show_emoji {
+            emoji::get_emoji_properties(ucd, code_point, flags);
             println!("----------------------");
         }
         if flags.show_name_info {
-            name_list::get_from_namelist(code_point, flags);
+            name_list::get_from_namelist(ucd, code_point, flags);
             println!("----------------------");
         }
     }
 }
 
+// The info of each code point, after that of the emoji sequence (flag, keycap, ZWJ...) starting with it, if any.
+// A sequence is named if the filters (`--since`, `--prop`...) keep at least one of its code points.
+fn print_code_points(ucd:&Ucd, code_points:&[u32], sequences:Option<&EmojiSequences>, keep:impl Fn(u32) -> bool, flags:&Flags) {
+    let mut sequence_end = 0;
+    for (index, code_point) in code_points.iter().enumerate() {
+        if index >= sequence_end {
+            if let Some(sequence) = sequences.and_then(|sequences| sequences.longest_at(&code_points[index..])) {
+                if sequence.code_points.iter().any(|code_point| keep(*code_point)) {
+                    emoji::print_sequence(sequence, flags);
+                }
+                sequence_end = index + sequence.code_points.len();
+            }
+        }
+        if keep(*code_point) {
+            get_char_info(ucd, *code_point, flags);
+        }
+    }
+}
+
+fn open_ucd() -> Ucd {
+    match Ucd::open(&file_utils::get_data_dir()) {
+        Ok(ucd) => ucd,
+        Err(message) => {
+            eprintln!("Error: {}", message);
+            exit(1);
+        }
+    }
+}
+
+// `unicode_info build-cache`, the cache is otherwise (re)built when needed, if the folder is writable.
+fn build_cache() {
+    let data_dir = file_utils::get_data_dir();
+    let cache_path = data_dir.join(cache::CACHE_FILE_NAME);
+    let result = ucd::build_cache(&data_dir)
+        .and_then(|bytes| cache::write(&cache_path, &bytes).map(|_| bytes.len()));
+    match result {
+        Ok(size) => println!("Wrote {} ({} bytes)", cache_path.display(), size),
+        Err(message) => {
+            eprintln!("Error: {}", message);
+            exit(1);
+        }
+    }
+}
+
+// `unicode_info find <text>` and / or `--name-regex <regex>`
+fn find_by_name(text:Option<&str>, flags:&Flags) {
+    let regex = flags.name_regex.as_ref().map(|pattern| {
+        RegexBuilder::new(pattern).case_insensitive(true).build().unwrap_or_else(|err| {
+            eprintln!("Error: invalid --name-regex: {}", err);
+            exit(1);
+        })
+    });
+    let ucd = open_ucd();
+    if !name_search::print_by_name(&ucd, &NameQuery::new(text, regex), flags) {
+        exit(1);
+    }
+}
+
+// `--since` / `--age`
+fn age_filter(ucd:&Ucd, flags:&Flags) -> Option<AgeFilter> {
+    if flags.since.is_none() && flags.age.is_none() {
+        return None;
+    }
+    if !ucd.has_ages() {
+        eprintln!("Error: --since and --age need DerivedAge.txt in the data folder");
+        exit(1);
+    }
+    match AgeFilter::new(flags.since.as_deref(), flags.age.as_deref()) {
+        Ok(filter) => Some(filter),
+        Err(message) => {
+            eprintln!("Error: {}", message);
+            exit(1);
+        }
+    }
+}
+
+// `--prop`
+fn property_filter(ucd:&Ucd, flags:&Flags) -> Option<PropertyFilter> {
+    if flags.properties.is_empty() {
+        return None;
+    }
+    match PropertyFilter::new(ucd, &flags.properties) {
+        Ok(filter) => Some(filter),
+        Err(message) => {
+            eprintln!("Error: {}", message);
+            exit(1);
+        }
+    }
+}
+
 fn main() {
-    let re_hex_cp:Regex = Regex::new(r"^([0-9A-Fa-f]{4,6})$").unwrap();
     let args: Vec<String> = env::args().collect();
 
     let flags = help::parse_args(&args);
-    for arg in &flags.rest {
-        let sarg = arg.as_str();
-        if re_hex_cp.is_match(sarg) {
-            let code_point = u32::from_str_radix(&sarg, 16).unwrap();
-            get_char_info(code_point, &flags);
-        } else {
-            for ch in sarg.chars() {
-                get_char_info(ch as u32, &flags);
+    // With --text, `find` or `width` are only words to show.
+    let command_args = if flags.input_mode == InputMode::Text { Vec::new() } else { flags.args() };
+    if command_args == ["build-cache"] {
+        build_cache();
+        return;
+    }
+    if command_args.first() == Some(&"find") || flags.name_regex.is_some() {
+        let text = command_args.iter().skip_while(|arg| **arg == "find").copied().collect::<Vec<&str>>().join(" ");
+        if text.is_empty() && flags.name_regex.is_none() {
+            eprintln!("Error: find needs a text, or --name-regex");
+            exit(1);
+        }
+        find_by_name(if text.is_empty() { None } else { Some(&text) }, &flags);
+        return;
+    }
+    let ucd = open_ucd();
+    if command_args.first() == Some(&"width") {
+        if command_args.len() < 2 {
+            eprintln!("Error: width needs a text");
+            exit(1);
+        }
+        if ucd.east_asian_width(0).is_none() {
+            eprintln!("Error: width needs EastAsianWidth.txt in the data folder");
+            exit(1);
+        }
+        width::print_width(&ucd, &command_args[1..].join(" "), &flags);
+        return;
+    }
+    if flags.list_blocks {
+        block_name::list_blocks(&ucd, &flags);
+        return;
+    }
+    let age_filter = age_filter(&ucd, &flags);
+    let property_filter = property_filter(&ucd, &flags);
+    let keep = |code_point:u32| {
+        age_filter.as_ref().is_none_or(|filter| filter.is_match(ucd.age(code_point)))
+            && property_filter.as_ref().is_none_or(|filter| filter.is_match(&ucd, code_point))
+    };
+    // All the inputs are checked before showing anything.
+    let mut selections:Vec<Selection> = Vec::new();
+    for input in &flags.inputs {
+        match input::parse_input(input, flags.input_mode, |name| block_name::find_block(&ucd, name)) {
+            Ok(selection) => selections.push(selection),
+            Err(message) => {
+                eprintln!("Error: {}", message);
+                exit(1);
             }
         }
     }
+    let sequences = flags.show_emoji.then(|| EmojiSequences::new(&ucd));
+    for selection in selections {
+        match selection {
+            Selection::CodePoints(code_points) => print_code_points(&ucd, &code_points, sequences.as_ref(), keep, &flags),
+            Selection::Range(first, last) => char_table::print_range(&ucd, first, last, keep, &flags),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -60,40 +231,34 @@ mod tests {
     #[test]
     fn test_string() {
         let flags = help::Flags::new(true);
+        let ucd = Ucd::open(&file_utils::get_data_dir()).unwrap();
         for c in "Hello 日本語 💩".chars() {
-            get_char_info(c as u32, &flags);
+            get_char_info(&ucd, c as u32, &flags);
         }
     }
 
     #[test]
     fn test_cp() {
         let flags = help::Flags::new(true);
-        get_char_info(0x0048, &flags); // LATIN CAPITAL LETTER H;Lu;0;L;;;;;N;;;;0068;
-        get_char_info(0x00FB, &flags); // LATIN SMALL LETTER U WITH CIRCUMFLEX;Ll;0;L;0075 0302;;;;N;LATIN SMALL LETTER U CIRCUMFLEX;;00DB;;00DB
-        get_char_info(0x00DA, &flags);
-        get_char_info(0x0664, &flags); // ARABIC-INDIC DIGIT FOUR;Nd;0;AN;;4;4;4;N;;;;;
-        get_char_info(0x0218, &flags); // LATIN CAPITAL LETTER S WITH COMMA BELOW;Lu;0;L;0053 0326;;;;N;;;;0219;
-        get_char_info(0x2743, &flags); // HEAVY TEARDROP-SPOKED PINWHEEL ASTERISK;So;0;ON;;;;;N;;;;;
-        get_char_info(0xA64F, &flags); // CYRILLIC SMALL LETTER NEUTRAL YER;Ll;0;L;;;;;N;;;A64E;;A64E
-        get_char_info(0xFD13, &flags); // ARABIC LIGATURE AIN WITH ALEF MAKSURA FINAL FORM;Lo;0;AL;<final> 0639 0649;;;;N;;;;;
-        get_char_info(0x1FBC3, &flags); // RIGHT THIRD WHITE RIGHT POINTING INDEX;So;0;ON;;;;;N;;;;;
-        get_char_info(0x2F80B, &flags); // CJK COMPATIBILITY IDEOGRAPH-2F80B;Lo;0;L;50CF;;;;N;;;;;
-        get_char_info(0xF0000, &flags); // <Plane 15 Private Use, First>;Co;0;L;;;;;N;;;;;
-        get_char_info(0xFFF00, &flags); // <Plane 15 Private Use, ?>;Co;0;L;;;;;N;;;;;
-        get_char_info(0xFFFFD, &flags); // <Plane 15 Private Use, Last>;Co;0;L;;;;;N;;;;;
-        get_char_info(0x100000, &flags); // <Plane 16 Private Use, First>;Co;0;L;;;;;N;;;;;
-        get_char_info(0x10FFFD, &flags); // <Plane 16 Private Use, Last>;Co;0;L;;;;;N;;;;;
+        let ucd = Ucd::open(&file_utils::get_data_dir()).unwrap();
+        get_char_info(&ucd, 0x0048, &flags); // LATIN CAPITAL LETTER H;Lu;0;L;;;;;N;;;;0068;
+        get_char_info(&ucd, 0x00FB, &flags); // LATIN SMALL LETTER U WITH CIRCUMFLEX;Ll;0;L;0075 0302;;;;N;LATIN SMALL LETTER U CIRCUMFLEX;;00DB;;00DB
+        get_char_info(&ucd, 0x00DA, &flags);
+        get_char_info(&ucd, 0x0664, &flags); // ARABIC-INDIC DIGIT FOUR;Nd;0;AN;;4;4;4;N;;;;;
+        get_char_info(&ucd, 0x0218, &flags); // LATIN CAPITAL LETTER S WITH COMMA BELOW;Lu;0;L;0053 0326;;;;N;;;;0219;
+        get_char_info(&ucd, 0x2743, &flags); // HEAVY TEARDROP-SPOKED PINWHEEL ASTERISK;So;0;ON;;;;;N;;;;;
+        get_char_info(&ucd, 0xA64F, &flags); // CYRILLIC SMALL LETTER NEUTRAL YER;Ll;0;L;;;;;N;;;A64E;;A64E
+        get_char_info(&ucd, 0xFD13, &flags); // ARABIC LIGATURE AIN WITH ALEF MAKSURA FINAL FORM;Lo;0;AL;<final> 0639 0649;;;;N;;;;;
+        get_char_info(&ucd, 0x1FBC3, &flags); // RIGHT THIRD WHITE RIGHT POINTING INDEX;So;0;ON;;;;;N;;;;;
+        get_char_info(&ucd, 0x2F80B, &flags); // CJK COMPATIBILITY IDEOGRAPH-2F80B;Lo;0;L;50CF;;;;N;;;;;
+        get_char_info(&ucd, 0xF0000, &flags); // <Plane 15 Private Use, First>;Co;0;L;;;;;N;;;;;
+        get_char_info(&ucd, 0xFFF00, &flags); // <Plane 15 Private Use, ?>;Co;0;L;;;;;N;;;;;
+        get_char_info(&ucd, 0xFFFFD, &flags); // <Plane 15 Private Use, Last>;Co;0;L;;;;;N;;;;;
+        get_char_info(&ucd, 0x100000, &flags); // <Plane 16 Private Use, First>;Co;0;L;;;;;N;;;;;
+        get_char_info(&ucd, 0x10FFFD, &flags); // <Plane 16 Private Use, Last>;Co;0;L;;;;;N;;;;;
         // Error, invalid code points
-        get_char_info(0xD83D, &flags);
-        get_char_info(0xDCA9, &flags);
-        get_char_info(0x12FAFD, &flags);
+        get_char_info(&ucd, 0xD83D, &flags);
+        get_char_info(&ucd, 0xDCA9, &flags);
+        get_char_info(&ucd, 0x12FAFD, &flags);
     }
 }
-
-/* TODO:
-- Command line
-  - decimal: <123 342 11> (?)
-  - utf-8  : <94 C3 D8 A1 65 49 32>
-  - utf-16 : <94C3 D8A1 6542>
-  - utf-32 : <000094C3 001D8A1>
- */
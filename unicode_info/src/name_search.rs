@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+
+use regex::Regex;
+
+use crate::char_table;
+use crate::help;
+use crate::ucd::Ucd;
+
+/// A character found by name, `alias` is the NamesList.txt line that matched when the name didn't.
+#[derive(Debug)]
+pub struct Hit<'a> {
+    pub code_point:u32,
+    pub name:&'a str,
+    pub general_category:&'a str,
+    pub alias:Option<&'a str>,
+}
+
+/// What to look for: a text (case insensitive substring), a regex, or both.
+pub struct NameQuery {
+    // Uppercase
+    text:Option<String>,
+    regex:Option<Regex>,
+}
+
+impl NameQuery {
+    pub fn new(text:Option<&str>, regex:Option<Regex>) -> NameQuery {
+        NameQuery { text: text.map(str::to_uppercase), regex }
+    }
+
+    fn is_match(&self, name:&str) -> bool {
+        let text_match = self.text.as_ref().is_none_or(|text| name.to_uppercase().contains(text));
+        text_match && self.regex.as_ref().is_none_or(|regex| regex.is_match(name))
+    }
+}
+
+// The NamesList.txt lines searched besides the name: aliases (=), formal aliases (%) and notes (*).
+// The cross references (x) name other characters, a hit there would be misleading.
+fn alias_text(line:&str) -> Option<&str> {
+    ["\t= ", "\t% ", "\t* "].iter().any(|prefix| line.starts_with(prefix)).then(|| line.trim_start())
+}
+
+/// The characters whose name, alias or annotation matches, ordered by code point.
+/// The characters of the ranges (CJK ideographs, Hangul syllables...) have no name to search.
+pub fn find_by_name<'a>(ucd:&'a Ucd, query:&NameQuery) -> Vec<Hit<'a>> {
+    let mut hits:BTreeMap<u32, Hit> = BTreeMap::new();
+    let mut categories:BTreeMap<u32, (&str, &str)> = BTreeMap::new();
+    for (code_point, fields) in ucd.all_unicode_data() {
+        if fields.len() < 3 {
+            continue;
+        }
+        categories.insert(code_point, (fields[1], fields[2]));
+        if query.is_match(fields[1]) {
+            hits.insert(code_point, Hit { code_point, name: fields[1], general_category: fields[2], alias: None });
+        }
+    }
+    for (code_point, lines) in ucd.all_names_list() {
+        if hits.contains_key(&code_point) {
+            continue;
+        }
+        let Some((name, general_category)) = categories.get(&code_point) else {
+            continue;
+        };
+        if let Some(alias) = lines.iter().filter_map(|line| alias_text(line)).find(|alias| query.is_match(alias)) {
+            hits.insert(code_point, Hit { code_point, name, general_category, alias: Some(alias) });
+        }
+    }
+    hits.into_values().collect()
+}
+
+/// Prints one line per hit: code point, glyph and name. Returns false if nothing was found.
+pub fn print_by_name(ucd:&Ucd, query:&NameQuery, flags:&help::Flags) -> bool {
+    let hits = find_by_name(ucd, query);
+    for hit in &hits {
+        let line = char_table::char_line(hit.code_point, hit.name, hit.general_category, flags);
+        match hit.alias {
+            Some(alias) => println!("{}  ({})", line, alias),
+            None => println!("{}", line),
+        }
+    }
+    !hits.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_by_name() {
+        let ucd = Ucd::from_texts(&[
+            ("UnicodeData.txt", "002D;HYPHEN-MINUS;Pd;0;ES;;;;;N;;;;;\n\
+                    00C7;LATIN CAPITAL LETTER C WITH CEDILLA;Lu;0;L;0043 0327;;;;N;;;;00E7;\n\
+                    2013;EN DASH;Pd;0;ON;;;;;N;;;;;\n\
+                    3400;<CJK Ideograph Extension A, First>;Lo;0;L;;;;;N;;;;;\n\
+                    4DBF;<CJK Ideograph Extension A, Last>;Lo;0;L;;;;;N;;;;;\n"),
+            ("NamesList.txt", "002D\tHYPHEN-MINUS\n\t= hyphen or minus sign\n\
+                    \tx (en dash - 2013)\n00C7\tLATIN CAPITAL LETTER C WITH CEDILLA\n2013\tEN DASH\n"),
+        ]);
+
+        let text = |text:&str| NameQuery::new(Some(text), None);
+        let code_points = |hits:Vec<Hit>| hits.iter().map(|hit| hit.code_point).collect::<Vec<u32>>();
+        assert_eq!(code_points(find_by_name(&ucd, &text("dash"))), vec![0x2013]);
+        assert_eq!(code_points(find_by_name(&ucd, &text("minus"))), vec![0x2D]);
+        assert_eq!(find_by_name(&ucd, &text("minus sign"))[0].alias, Some("= hyphen or minus sign"));
+        assert!(find_by_name(&ucd, &text("ideograph")).is_empty());
+        let regex = NameQuery::new(None, Some(Regex::new("LATIN .* WITH CEDILLA").unwrap()));
+        assert_eq!(code_points(find_by_name(&ucd, &regex)), vec![0xC7]);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::color_utils;
+use crate::help;
+use crate::ucd::{self, Ucd};
+
+/// `--prop`: the code points having all the properties. The names are those of PropList.txt and
+/// DerivedCoreProperties.txt, matched ignoring case, spaces, underscores and hyphens: `xid_start`.
+pub struct PropertyFilter {
+    properties:Vec<String>,
+}
+
+impl PropertyFilter {
+    pub fn new(ucd:&Ucd, names:&[String]) -> Result<PropertyFilter, String> {
+        let known = ucd.binary_property_names();
+        if known.is_empty() {
+            return Err("--prop needs PropList.txt or DerivedCoreProperties.txt in the data folder".to_string());
+        }
+        let mut properties = Vec::new();
+        for name in names {
+            match known.iter().find(|property| ucd::loose_name(property) == ucd::loose_name(name)) {
+                Some(property) => properties.push(property.to_string()),
+                None => return Err(format!("unknown property '{}'", name)),
+            }
+        }
+        Ok(PropertyFilter { properties })
+    }
+
+    pub fn is_match(&self, ucd:&Ucd, code_point:u32) -> bool {
+        let properties = ucd.binary_properties(code_point);
+        self.properties.iter().all(|property| properties.contains(&property.as_str()))
+    }
+}
+
+pub fn get_binary_properties(ucd:&Ucd, code_point:u32, flags:&help::Flags) {
+    let properties = ucd.binary_properties(code_point);
+    let label = color_utils::label(&"Binary Properties:".to_string(), flags);
+    if !ucd.has_binary_properties() {
+        println!("  {} (no PropList.txt, DerivedCoreProperties.txt)", label);
+    } else if properties.is_empty() {
+        println!("  {} -", label);
+    } else {
+        println!("  {} {}", label, properties.join(", "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_property_filter() {
+        let ucd = Ucd::from_texts(&[
+            ("PropList.txt", "0009..000D    ; White_Space # Cc   [5] <control-0009>..\n\
+                    0041..0046    ; Hex_Digit # L&   [6] LATIN CAPITAL LETTER A..LATIN CAPITAL LETTER F\n"),
+            ("DerivedCoreProperties.txt", "0041..005A    ; Alphabetic # L&  [26] LATIN CAPITAL LETTER A..\n\
+                    0041..005A    ; ID_Start # L&  [26] LATIN CAPITAL LETTER A..\n"),
+        ]);
+
+        assert_eq!(ucd.binary_properties(0x41), vec!["Alphabetic", "Hex_Digit", "ID_Start"]);
+        assert!(ucd.binary_properties(0x20).is_empty());
+        let filter = PropertyFilter::new(&ucd, &["id-start".to_string(), "HEX_DIGIT".to_string()]).unwrap();
+        assert!(filter.is_match(&ucd, 0x46));
+        assert!(!filter.is_match(&ucd, 0x47));
+        assert!(PropertyFilter::new(&ucd, &["Math".to_string()]).is_err());
+    }
+}
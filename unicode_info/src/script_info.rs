@@ -0,0 +1,46 @@
+use crate::color_utils;
+use crate::help;
+use crate::ucd::Ucd;
+
+// `Latn ; Latin`, or what is known without PropertyValueAliases.txt.
+fn script_names(ucd:&Ucd, script:&str) -> String {
+    match ucd.value_aliases("sc", script) {
+        Some(aliases) if aliases.len() > 1 => format!("{} ; {}", aliases[0], aliases[1]),
+        _ => script.to_string(),
+    }
+}
+
+pub fn get_script_info(ucd:&Ucd, code_point:u32, flags:&help::Flags) {
+    let Some(script) = ucd.script(code_point) else {
+        println!("  {} (no Scripts.txt)", color_utils::label(&"Script (sc)            :".to_string(), flags));
+        return;
+    };
+    println!("  {} {}", color_utils::label(&"Script (sc)            :".to_string(), flags), script_names(ucd, script));
+    // Without an entry in ScriptExtensions.txt, the extensions are the script itself.
+    let extensions = match ucd.script_extensions(code_point) {
+        Some(scripts) => scripts.iter().map(|script| script_names(ucd, script)).collect::<Vec<String>>().join(", "),
+        None => script_names(ucd, script),
+    };
+    println!("  {} {}", color_utils::label(&"Script_Extensions (scx):".to_string(), flags), extensions);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_names() {
+        let ucd = Ucd::from_texts(&[
+            ("PropertyValueAliases.txt", "sc ; Arab ; Arabic\nsc ; Latn ; Latin\nsc ; Syrc ; Syriac\n"),
+            ("Scripts.txt", "0041..005A    ; Latin # L&  [26] LATIN CAPITAL LETTER A..\n"),
+            ("ScriptExtensions.txt", "064B..0655    ; Arab Syrc # Mn  [11] ARABIC FATHATAN..\n"),
+        ]);
+
+        assert_eq!(ucd.script(0x41), Some("Latin"));
+        assert_eq!(ucd.script(0x30), Some("Unknown"));
+        assert_eq!(script_names(&ucd, "Latin"), "Latn ; Latin");
+        assert_eq!(script_names(&ucd, "Unknown"), "Unknown");
+        assert_eq!(ucd.script_extensions(0x64C), Some(vec!["Arab", "Syrc"]));
+        assert_eq!(ucd.script_extensions(0x41), None);
+    }
+}
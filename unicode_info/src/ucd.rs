@@ -0,0 +1,507 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::cache::{self, CacheData, Freshness, SourceStamp, Table, CACHE_FILE_NAME};
+
+const FILE_UNICODE_DATA:&str = "UnicodeData.txt";
+const FILE_BLOCKS:&str = "Blocks.txt";
+const FILE_NAMES_LIST:&str = "NamesList.txt";
+const FILE_PROPERTY_VALUE_ALIASES:&str = "PropertyValueAliases.txt";
+const FILE_SCRIPTS:&str = "Scripts.txt";
+const FILE_SCRIPT_EXTENSIONS:&str = "ScriptExtensions.txt";
+const FILE_DERIVED_AGE:&str = "DerivedAge.txt";
+const FILE_PROP_LIST:&str = "PropList.txt";
+const FILE_DERIVED_CORE_PROPERTIES:&str = "DerivedCoreProperties.txt";
+const FILE_EAST_ASIAN_WIDTH:&str = "EastAsianWidth.txt";
+const FILE_EMOJI_DATA:&str = "emoji-data.txt";
+const FILE_EMOJI_SEQUENCES:&str = "emoji-sequences.txt";
+const FILE_EMOJI_ZWJ_SEQUENCES:&str = "emoji-zwj-sequences.txt";
+
+struct Source {
+    name:&'static str,
+    parse:fn(&str) -> Table,
+    /// The other files were added later, an older data folder may not have them.
+    required:bool,
+}
+
+// One table per file, in this order in the cache.
+const SOURCES:&[Source] = &[
+    Source { name: FILE_UNICODE_DATA, parse: parse_unicode_data, required: true },
+    Source { name: FILE_BLOCKS, parse: parse_blocks, required: true },
+    Source { name: FILE_NAMES_LIST, parse: parse_names_list, required: true },
+    Source { name: FILE_PROPERTY_VALUE_ALIASES, parse: parse_property_value_aliases, required: false },
+    Source { name: FILE_SCRIPTS, parse: parse_property_file, required: false },
+    Source { name: FILE_SCRIPT_EXTENSIONS, parse: parse_property_file, required: false },
+    Source { name: FILE_DERIVED_AGE, parse: parse_property_file, required: false },
+    Source { name: FILE_PROP_LIST, parse: parse_binary_properties, required: false },
+    Source { name: FILE_DERIVED_CORE_PROPERTIES, parse: parse_binary_properties, required: false },
+    Source { name: FILE_EAST_ASIAN_WIDTH, parse: parse_property_file_with_defaults, required: false },
+    Source { name: FILE_EMOJI_DATA, parse: parse_binary_properties, required: false },
+    Source { name: FILE_EMOJI_SEQUENCES, parse: parse_emoji_sequences, required: false },
+    Source { name: FILE_EMOJI_ZWJ_SEQUENCES, parse: parse_emoji_sequences, required: false },
+];
+const TABLE_UNICODE_DATA:usize = 0;
+const TABLE_BLOCKS:usize = 1;
+const TABLE_NAMES_LIST:usize = 2;
+const TABLE_PROPERTY_VALUE_ALIASES:usize = 3;
+const TABLE_SCRIPTS:usize = 4;
+const TABLE_SCRIPT_EXTENSIONS:usize = 5;
+const TABLE_DERIVED_AGE:usize = 6;
+const TABLE_PROP_LIST:usize = 7;
+const TABLE_DERIVED_CORE_PROPERTIES:usize = 8;
+const TABLE_EAST_ASIAN_WIDTH:usize = 9;
+const TABLE_EMOJI_DATA:usize = 10;
+const TABLE_EMOJI_SEQUENCES:usize = 11;
+const TABLE_EMOJI_ZWJ_SEQUENCES:usize = 12;
+
+/// The Unicode Character Database, from the cache compiled from the UCD files.
+pub struct Ucd {
+    cache:CacheData,
+}
+
+/// Property and value names are matched ignoring case, spaces, underscores and hyphens (UAX #44 LM3).
+pub fn loose_name(name:&str) -> String {
+    name.chars().filter(|chr| !matches!(chr, ' ' | '_' | '-')).flat_map(char::to_lowercase).collect()
+}
+
+fn parse_hex(text:&str) -> Option<u32> {
+    u32::from_str_radix(text.trim(), 16).ok()
+}
+
+/// The lines of UnicodeData.txt. A `<..., First>` / `<..., Last>` pair of lines is one range,
+/// with the line of the first code point and the name as `<CJK Ideograph>`.
+pub fn parse_unicode_data(text:&str) -> Table {
+    let mut result:Table = Vec::new();
+    for line in text.lines() {
+        let Some((cp_str, rest)) = line.split_once(';') else {
+            continue;
+        };
+        let Some(code_point) = parse_hex(cp_str) else {
+            continue;
+        };
+        if rest.contains(", Last>;") {
+            // Closes the range opened by the previous `<..., First>` line.
+            if let Some((_, last, first_line)) = result.last_mut() {
+                if first_line.contains(", First>;") {
+                    *last = code_point;
+                    *first_line = first_line.replace(", First>", ">");
+                }
+            }
+            continue;
+        }
+        result.push((code_point, code_point, line.to_string()));
+    }
+    result
+}
+
+/// The block names of Blocks.txt.
+pub fn parse_blocks(text:&str) -> Table {
+    let mut result:Table = Vec::new();
+    for line in text.lines() {
+        // 0000..007F; Basic Latin
+        let Some((range, name)) = line.split_once("; ") else {
+            continue;
+        };
+        let Some((first, last)) = range.split_once("..") else {
+            continue;
+        };
+        if let (Some(first), Some(last)) = (parse_hex(first), parse_hex(last)) {
+            result.push((first, last, name.trim().to_string()));
+        }
+    }
+    result
+}
+
+/// The lines of NamesList.txt about each code point: its own line, then the annotations.
+pub fn parse_names_list(text:&str) -> Table {
+    let mut result:Table = Vec::new();
+    let mut in_entry = false;
+    for line in text.lines() {
+        // 0041	LATIN CAPITAL LETTER A
+        let code_point = line.split_once('\t').and_then(|(cp_str, _)| parse_hex(cp_str));
+        if let Some(code_point) = code_point {
+            result.push((code_point, code_point, line.to_string()));
+            in_entry = true;
+        } else if in_entry && (line.starts_with('\t') || line.starts_with('@')) {
+            if let Some((_, _, lines)) = result.last_mut() {
+                lines.push('\n');
+                lines.push_str(line);
+            }
+        } else {
+            in_entry = false;
+        }
+    }
+    result.sort_by_key(|(code_point, _, _)| *code_point);
+    result
+}
+
+// The data lines of a UCD file, without the comments.
+fn data_lines(text:&str) -> impl Iterator<Item = &str> {
+    text.lines()
+        .map(|line| line.split_once('#').map_or(line, |(data, _)| data).trim())
+        .filter(|line| !line.is_empty())
+}
+
+/// A file in the usual UCD format, `0041..005A    ; Latin # L&  [26] LATIN CAPITAL LETTER A..`,
+/// with one value per code point. The values are the fields after the range.
+pub fn parse_property_file(text:&str) -> Table {
+    let mut result:Table = Vec::new();
+    for line in data_lines(text) {
+        let Some((range, value)) = line.split_once(';') else {
+            continue;
+        };
+        let (first, last) = range.trim().split_once("..").unwrap_or((range.trim(), range.trim()));
+        if let (Some(first), Some(last)) = (parse_hex(first), parse_hex(last)) {
+            let fields:Vec<&str> = value.split(';').map(str::trim).collect();
+            result.push((first, last, fields.join(";")));
+        }
+    }
+    // The files are grouped by value, not sorted by code point.
+    result.sort_by_key(|(first, _, _)| *first);
+    result
+}
+
+/// A property file with its defaults, the `# @missing: 3400..4DBF; W` lines (UAX #44, 4.2.10),
+/// for the code points not listed. A later @missing line overrides the previous ones in its range.
+pub fn parse_property_file_with_defaults(text:&str) -> Table {
+    let defaults:String = text.lines()
+        .filter_map(|line| line.strip_prefix("# @missing:"))
+        .flat_map(|line| [line, "\n"])
+        .collect();
+    let mut result:Table = Vec::new();
+    for (first, last, value) in parse_property_file(&defaults).into_iter().chain(parse_property_file(text)) {
+        set_range(&mut result, first, last, value);
+    }
+    result
+}
+
+// Sets the value of a range in a sorted table, cutting the ranges it overlaps.
+fn set_range(table:&mut Table, first:u32, last:u32, value:String) {
+    let mut cut:Table = Vec::new();
+    for (range_first, range_last, range_value) in table.drain(..) {
+        if range_last < first || range_first > last {
+            cut.push((range_first, range_last, range_value));
+            continue;
+        }
+        if range_first < first {
+            cut.push((range_first, first - 1, range_value.clone()));
+        }
+        if range_last > last {
+            cut.push((last + 1, range_last, range_value));
+        }
+    }
+    cut.push((first, last, value));
+    cut.sort_by_key(|(first, _, _)| *first);
+    *table = cut;
+}
+
+/// A file of binary properties, `0009..000D    ; White_Space # Cc   [5] <control-0009>..`.
+/// A code point can have several properties, the ranges overlap: they are cut where a property
+/// starts or ends, with the properties of each piece joined with ';'. The few properties with
+/// a value, `094D ; InCB; Linker` (Indic_Conjunct_Break), are not binary and are skipped.
+pub fn parse_binary_properties(text:&str) -> Table {
+    // The properties starting (true) and ending (false) at each code point.
+    let mut changes:BTreeMap<u32, Vec<(bool, String)>> = BTreeMap::new();
+    for (first, last, property) in parse_property_file(text) {
+        if property.contains(';') {
+            continue;
+        }
+        changes.entry(first).or_default().push((true, property.clone()));
+        changes.entry(last + 1).or_default().push((false, property));
+    }
+    let mut result:Table = Vec::new();
+    let mut active:BTreeMap<String, u32> = BTreeMap::new();
+    let mut changes = changes.into_iter().peekable();
+    while let Some((first, properties)) = changes.next() {
+        for (starts, property) in properties {
+            let count = active.entry(property.clone()).or_insert(0);
+            if starts {
+                *count += 1;
+            } else {
+                *count -= 1;
+                if *count == 0 {
+                    active.remove(&property);
+                }
+            }
+        }
+        let Some((next, _)) = changes.peek() else {
+            break;
+        };
+        if active.is_empty() {
+            continue;
+        }
+        let value = active.keys().cloned().collect::<Vec<String>>().join(";");
+        match result.last_mut() {
+            Some((_, last, previous)) if *last + 1 == first && *previous == value => *last = next - 1,
+            _ => result.push((first, next - 1, value)),
+        }
+    }
+    result
+}
+
+/// The sequences of emoji-sequences.txt and emoji-zwj-sequences.txt,
+/// `1F468 200D 1F469 ; RGI_Emoji_ZWJ_Sequence ; couple: man, woman # E2.0 ...` as
+/// `1F468 200D 1F469;RGI_Emoji_ZWJ_Sequence;couple: man, woman`, keyed by their index like the aliases.
+/// The ranges of single characters (`231A..231B ; Basic_Emoji`) are not sequences and are skipped.
+pub fn parse_emoji_sequences(text:&str) -> Table {
+    data_lines(text)
+        .filter(|line| !line.contains(".."))
+        .map(|line| line.split(';').map(str::trim).collect::<Vec<&str>>().join(";"))
+        .enumerate()
+        .map(|(index, line)| (index as u32, index as u32, line))
+        .collect()
+}
+
+/// The lines of PropertyValueAliases.txt, `sc ; Latn ; Latin`, as `sc;Latn;Latin`.
+/// Not about code points, the "code point" of a line is its index.
+pub fn parse_property_value_aliases(text:&str) -> Table {
+    data_lines(text).enumerate()
+        .map(|(index, line)| {
+            let fields:Vec<&str> = line.split(';').map(str::trim).collect();
+            (index as u32, index as u32, fields.join(";"))
+        })
+        .collect()
+}
+
+/// Parses the UCD files, into the content of a cache file.
+pub fn build_cache(data_dir:&Path) -> Result<Vec<u8>, String> {
+    let mut sources = Vec::new();
+    let mut tables = Vec::new();
+    for source in SOURCES {
+        if !source.required && !data_dir.join(source.name).is_file() {
+            tables.push(Vec::new());
+            sources.push(SourceStamp::missing(source.name));
+            continue;
+        }
+        let (text, stamp) = cache::read_source(data_dir, source.name)?;
+        tables.push((source.parse)(&text));
+        sources.push(stamp);
+    }
+    Ok(cache::serialize(&sources, &tables))
+}
+
+impl Ucd {
+    /// Maps the cache of the data folder, (re)building it first if a UCD file changed.
+    /// A cache installed without the text files it was built from is used as it is.
+    pub fn open(data_dir:&Path) -> Result<Ucd, String> {
+        let cache_path = data_dir.join(CACHE_FILE_NAME);
+        let names:Vec<&str> = SOURCES.iter().map(|source| source.name).collect();
+        let mapped = CacheData::map(&cache_path).ok();
+        if let Some(cache) = mapped {
+            match cache.freshness(data_dir, &names) {
+                Freshness::Fresh => return Ok(Ucd { cache }),
+                Freshness::Touched(stamps) => {
+                    let _ = cache::write(&cache_path, &cache.restamped(&stamps));
+                    return Ok(Ucd { cache });
+                }
+                Freshness::Stale => {
+                    if !SOURCES.iter().any(|source| source.required && data_dir.join(source.name).is_file()) {
+                        return Ok(Ucd { cache });
+                    }
+                }
+            }
+        }
+        let bytes = build_cache(data_dir)?;
+        // The data folder may be read only (installed), the cache is then only used for this run.
+        let _ = cache::write(&cache_path, &bytes);
+        Ok(Ucd { cache: CacheData::from_bytes(bytes)? })
+    }
+
+    /// The 15 fields of the UnicodeData.txt line, `[1]` is the name.
+    pub fn unicode_data(&self, code_point:u32) -> Option<Vec<&str>> {
+        self.cache.lookup(TABLE_UNICODE_DATA, code_point).map(|line| line.split(';').collect())
+    }
+
+    pub fn block(&self, code_point:u32) -> Option<&str> {
+        self.cache.lookup(TABLE_BLOCKS, code_point)
+    }
+
+    /// The code points of UnicodeData.txt with their fields, without the ranges (`<CJK Ideograph>`).
+    pub fn all_unicode_data(&self) -> impl Iterator<Item = (u32, Vec<&str>)> {
+        self.cache.entries(TABLE_UNICODE_DATA)
+            .filter(|(first, last, _)| first == last)
+            .map(|(code_point, _, line)| (code_point, line.split(';').collect()))
+    }
+
+    /// The (first, last) code points of all the entries of UnicodeData.txt, ranges included.
+    pub fn assigned_ranges(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.cache.entries(TABLE_UNICODE_DATA).map(|(first, last, _)| (first, last))
+    }
+
+    /// The blocks of Blocks.txt, as (first, last, name).
+    pub fn all_blocks(&self) -> impl Iterator<Item = (u32, u32, &str)> {
+        self.cache.entries(TABLE_BLOCKS)
+    }
+
+    /// The names of a property value, short one first: `sc`, `Latin` => `["Latn", "Latin"]`.
+    /// The value is matched ignoring case, spaces, underscores and hyphens.
+    pub fn value_aliases(&self, property:&str, value:&str) -> Option<Vec<&str>> {
+        let value = loose_name(value);
+        self.cache.entries(TABLE_PROPERTY_VALUE_ALIASES)
+            .map(|(_, _, line)| line.split(';').collect::<Vec<&str>>())
+            .find(|fields| fields.len() > 2 && fields[0] == property && fields[1..].iter().any(|alias| loose_name(alias) == value))
+            .map(|fields| fields[1..].to_vec())
+    }
+
+    /// The Script property (long name), `Unknown` if not in Scripts.txt, `None` without the file.
+    pub fn script(&self, code_point:u32) -> Option<&str> {
+        self.cache.entries(TABLE_SCRIPTS).next()?;
+        Some(self.cache.lookup(TABLE_SCRIPTS, code_point).unwrap_or("Unknown"))
+    }
+
+    /// The Script_Extensions property (short names), `None` if it is only the script.
+    pub fn script_extensions(&self, code_point:u32) -> Option<Vec<&str>> {
+        self.cache.lookup(TABLE_SCRIPT_EXTENSIONS, code_point).map(|scripts| scripts.split_whitespace().collect())
+    }
+
+    /// The Unicode version the code point was assigned in, `15.1`. `None` if unassigned or without DerivedAge.txt.
+    pub fn age(&self, code_point:u32) -> Option<&str> {
+        self.cache.lookup(TABLE_DERIVED_AGE, code_point)
+    }
+
+    pub fn has_ages(&self) -> bool {
+        self.cache.entries(TABLE_DERIVED_AGE).next().is_some()
+    }
+
+    /// The East_Asian_Width property (short name), with the @missing defaults of EastAsianWidth.txt
+    /// for the code points it doesn't list (`W` in the CJK ranges). `None` without the file.
+    pub fn east_asian_width(&self, code_point:u32) -> Option<&str> {
+        self.cache.entries(TABLE_EAST_ASIAN_WIDTH).next()?;
+        Some(self.cache.lookup(TABLE_EAST_ASIAN_WIDTH, code_point).unwrap_or("N"))
+    }
+
+    /// The properties of PropList.txt and DerivedCoreProperties.txt the code point has, sorted.
+    pub fn binary_properties(&self, code_point:u32) -> Vec<&str> {
+        let mut result:Vec<&str> = [TABLE_PROP_LIST, TABLE_DERIVED_CORE_PROPERTIES].iter()
+            .filter_map(|table| self.cache.lookup(*table, code_point))
+            .flat_map(|properties| properties.split(';'))
+            .collect();
+        result.sort_unstable();
+        result
+    }
+
+    pub fn has_binary_properties(&self) -> bool {
+        [TABLE_PROP_LIST, TABLE_DERIVED_CORE_PROPERTIES].iter().any(|table| self.cache.entries(*table).next().is_some())
+    }
+
+    /// All the properties of PropList.txt and DerivedCoreProperties.txt, sorted.
+    pub fn binary_property_names(&self) -> Vec<&str> {
+        let mut result:Vec<&str> = [TABLE_PROP_LIST, TABLE_DERIVED_CORE_PROPERTIES].iter()
+            .flat_map(|table| self.cache.entries(*table))
+            .flat_map(|(_, _, properties)| properties.split(';'))
+            .collect();
+        result.sort_unstable();
+        result.dedup();
+        result
+    }
+
+    /// The properties of emoji-data.txt the code point has: `Emoji`, `Extended_Pictographic`...
+    pub fn emoji_properties(&self, code_point:u32) -> Option<Vec<&str>> {
+        self.cache.entries(TABLE_EMOJI_DATA).next()?;
+        Some(self.cache.lookup(TABLE_EMOJI_DATA, code_point).map_or_else(Vec::new, |properties| properties.split(';').collect()))
+    }
+
+    /// The sequences of emoji-sequences.txt and emoji-zwj-sequences.txt, as (code points, type, name).
+    pub fn emoji_sequences(&self) -> impl Iterator<Item = (Vec<u32>, &str, &str)> {
+        [TABLE_EMOJI_SEQUENCES, TABLE_EMOJI_ZWJ_SEQUENCES].into_iter()
+            .flat_map(|table| self.cache.entries(table))
+            .filter_map(|(_, _, line)| {
+                let mut fields = line.split(';');
+                let code_points = fields.next()?.split_whitespace().map(parse_hex).collect::<Option<Vec<u32>>>()?;
+                Some((code_points, fields.next()?, fields.next()?))
+            })
+    }
+
+    /// A Ucd built in memory from (file name, text) pairs, for the tests. The other files are empty.
+    #[cfg(test)]
+    pub fn from_texts(files:&[(&str, &str)]) -> Ucd {
+        let mut sources = Vec::new();
+        let mut tables = Vec::new();
+        for source in SOURCES {
+            let text = files.iter().find(|(name, _)| *name == source.name).map_or("", |(_, text)| *text);
+            tables.push((source.parse)(text));
+            sources.push(SourceStamp::missing(source.name));
+        }
+        Ucd { cache: CacheData::from_bytes(cache::serialize(&sources, &tables)).unwrap() }
+    }
+
+    /// The code points of NamesList.txt with their lines.
+    pub fn all_names_list(&self) -> impl Iterator<Item = (u32, Vec<&str>)> {
+        self.cache.entries(TABLE_NAMES_LIST).map(|(code_point, _, lines)| (code_point, lines.lines().collect()))
+    }
+
+    /// The NamesList.txt line of the code point, followed by its annotations.
+    pub fn names_list(&self, code_point:u32) -> Option<Vec<&str>> {
+        self.cache.lookup(TABLE_NAMES_LIST, code_point).map(|lines| lines.lines().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let unicode_data = parse_unicode_data("0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;\n\
+                3400;<CJK Ideograph Extension A, First>;Lo;0;L;;;;;N;;;;;\n\
+                4DBF;<CJK Ideograph Extension A, Last>;Lo;0;L;;;;;N;;;;;\n\
+                4DC0;HEXAGRAM FOR THE CREATIVE HEAVEN;So;0;ON;;;;;N;;;;;\n");
+        assert_eq!(unicode_data.len(), 3);
+        assert_eq!(unicode_data[1].0, 0x3400);
+        assert_eq!(unicode_data[1].1, 0x4DBF);
+        assert!(unicode_data[1].2.starts_with("3400;<CJK Ideograph Extension A>;"));
+
+        let blocks = parse_blocks("# Blocks-15.1.0.txt\n0000..007F; Basic Latin\n0080..00FF; Latin-1 Supplement\n");
+        assert_eq!(blocks, vec![(0x00, 0x7F, "Basic Latin".to_string()), (0x80, 0xFF, "Latin-1 Supplement".to_string())]);
+
+        let names_list = parse_names_list("@@\t0000\tC0 Controls\n0041\tLATIN CAPITAL LETTER A\n\
+                \tx (latin small letter a - 0061)\n@\t\tSubheader\n0042\tLATIN CAPITAL LETTER B\n");
+        assert_eq!(names_list.len(), 2);
+        assert_eq!(names_list[0].2, "0041\tLATIN CAPITAL LETTER A\n\tx (latin small letter a - 0061)\n@\t\tSubheader");
+
+        let scripts = parse_property_file("# Scripts-15.1.0.txt\n0370..0373    ; Greek # L&   [4] GREEK CAPITAL LETTER HETA..\n\n\
+                0041..005A    ; Latin # L&  [26] LATIN CAPITAL LETTER A..LATIN CAPITAL LETTER Z\n\
+                0640          ; Common # Lm       ARABIC TATWEEL\n");
+        assert_eq!(scripts, vec![(0x41, 0x5A, "Latin".to_string()), (0x370, 0x373, "Greek".to_string()), (0x640, 0x640, "Common".to_string())]);
+
+        let aliases = parse_property_value_aliases("# PropertyValueAliases-15.1.0.txt\nsc ; Latn ; Latin\nsc ; Zyyy ; Common\n");
+        assert_eq!(aliases, vec![(0, 0, "sc;Latn;Latin".to_string()), (1, 1, "sc;Zyyy;Common".to_string())]);
+
+        let widths = parse_property_file_with_defaults("# @missing: 0000..10FFFF; N\n\
+                # @missing: 3400..4DBF; W\n0041 ; Na # Lu\n3400..3402 ; W # Lo\n3405 ; N # Lo\n");
+        assert_eq!(widths, vec![
+            (0, 0x40, "N".to_string()),
+            (0x41, 0x41, "Na".to_string()),
+            (0x42, 0x33FF, "N".to_string()),
+            (0x3400, 0x3402, "W".to_string()),
+            (0x3403, 0x3404, "W".to_string()),
+            (0x3405, 0x3405, "N".to_string()),
+            (0x3406, 0x4DBF, "W".to_string()),
+            (0x4DC0, 0x10FFFF, "N".to_string()),
+        ]);
+
+        let properties = parse_binary_properties("0009..000D    ; White_Space # Cc   [5] <control-0009>..<control-000D>\n\
+                0020          ; White_Space # Zs       SPACE\n\
+                002D          ; Dash # Pd       HYPHEN-MINUS\n\
+                000A..000B    ; Pattern_White_Space # Cc   [2] <control-000A>..<control-000B>\n\
+                0021..0022    ; Pattern_Syntax # Po   [2] EXCLAMATION MARK..QUOTATION MARK\n\
+                094D          ; InCB; Linker # Mn       DEVANAGARI SIGN VIRAMA\n");
+        assert_eq!(properties, vec![
+            (0x09, 0x09, "White_Space".to_string()),
+            (0x0A, 0x0B, "Pattern_White_Space;White_Space".to_string()),
+            (0x0C, 0x0D, "White_Space".to_string()),
+            (0x20, 0x20, "White_Space".to_string()),
+            (0x21, 0x22, "Pattern_Syntax".to_string()),
+            (0x2D, 0x2D, "Dash".to_string()),
+        ]);
+
+        let sequences = parse_emoji_sequences("# emoji-sequences.txt\n\
+                231A..231B    ; Basic_Emoji                  ; watch                                                          # E0.6   [2] (⌚..⌛)\n\
+                0023 FE0F 20E3; Emoji_Keycap_Sequence        ; keycap: \\x{23}                                                # E0.6   [1] (#️⃣)\n\
+                1F1EB 1F1F7   ; RGI_Emoji_Flag_Sequence      ; flag: France                                                   # E0.6   [1] (🇫🇷)\n");
+        assert_eq!(sequences, vec![
+            (0, 0, "0023 FE0F 20E3;Emoji_Keycap_Sequence;keycap: \\x{23}".to_string()),
+            (1, 1, "1F1EB 1F1F7;RGI_Emoji_Flag_Sequence;flag: France".to_string()),
+        ]);
+    }
+}
@@ -1,10 +1,6 @@
 use crate::color_utils;
-use crate::file_utils;
 use crate::help;
-
-const FILE_UNICODE_DATA:&str = "UnicodeData.txt";
-
-const BAD_CODE_POINT:u32 = 0xffffffff;
+use crate::ucd::Ucd;
 
 // Bidi_Class (bc)
 fn bc_to_string(short_id:&str) -> &str {
@@ -213,34 +209,8 @@ fn write_cp_entry(parts: Vec<&str>, flags:&help::Flags) {
     }
 }
 
-pub fn get_unicode_data(code_point:u32, flags:&help::Flags) {
-    let cp_str = format!("{:04X}", code_point);
-    let data_path = file_utils::get_data_path(&FILE_UNICODE_DATA.to_string());
-    let lines = file_utils::read_lines(&data_path);
-    let mut start_cp:u32 = BAD_CODE_POINT;
-    for line in lines {
-        let line_content = line.unwrap();
-        if line_content.starts_with(&cp_str) {
-            let parts: Vec<&str> = line_content.split(';').collect();
-            if parts[0].eq(&cp_str) {
-                write_cp_entry(parts, flags);
-                return;
-            }
-        } else if line_content.contains(" First>;") {
-            let parts: Vec<&str> = line_content.split(';').collect();
-            start_cp = u32::from_str_radix(&parts[0], 16).unwrap();
-        } else if line_content.contains(" Last>;") {
-            let mut parts: Vec<&str> = line_content.split(';').collect();
-            let end_cp = u32::from_str_radix(&parts[0], 16).unwrap();
-            if start_cp != BAD_CODE_POINT {
-                if start_cp <= code_point && code_point <= end_cp {
-                    let tmp = parts[1].replace(", Last>", ">");
-                    parts[1] = tmp.as_str();
-                    write_cp_entry(parts, flags);
-                    return;
-                }
-            }
-            start_cp = BAD_CODE_POINT;
-        }
+pub fn get_unicode_data(ucd:&Ucd, code_point:u32, flags:&help::Flags) {
+    if let Some(fields) = ucd.unicode_data(code_point) {
+        write_cp_entry(fields, flags);
     }
 }
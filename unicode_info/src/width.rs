@@ -0,0 +1,155 @@
+use crate::char_table;
+use crate::color_utils;
+use crate::help;
+use crate::ucd::Ucd;
+
+const ZERO_WIDTH_JOINER:u32 = 0x200D;
+const VARIATION_SELECTOR_16:u32 = 0xFE0F;
+
+fn is_regional_indicator(code_point:u32) -> bool {
+    (0x1F1E6..=0x1F1FF).contains(&code_point)
+}
+
+/// The columns of a code point on its own: 0 for controls, marks, formats and default ignorables,
+/// 2 for the wide and fullwidth ones (the emoji shown as emoji by default are wide), else 1.
+pub fn char_width(ucd:&Ucd, code_point:u32, ambiguous_wide:bool) -> u32 {
+    let general_category = ucd.unicode_data(code_point).and_then(|fields| fields.get(2).copied()).unwrap_or("Cn");
+    // The Hangul medial vowels and final consonants are combined with the initial consonant.
+    if matches!(general_category, "Cc" | "Cf" | "Mn" | "Me") || (0x1160..=0x11FF).contains(&code_point)
+            || ucd.binary_properties(code_point).contains(&"Default_Ignorable_Code_Point") {
+        return 0;
+    }
+    match ucd.east_asian_width(code_point) {
+        Some("W") | Some("F") => 2,
+        Some("A") if ambiguous_wide => 2,
+        _ => 1,
+    }
+}
+
+fn has_emoji_property(ucd:&Ucd, code_point:u32, property:&str) -> bool {
+    ucd.emoji_properties(code_point).is_some_and(|properties| properties.contains(&property))
+}
+
+/// The columns of each character of the text, in a terminal. Besides `char_width`, an emoji cluster
+/// is one glyph: VS16 (emoji presentation) makes its base wide if it is an emoji, a skin tone modifier and the
+/// characters joined to a wide base by ZWJ add nothing, and a pair of regional indicators is one wide flag.
+pub fn text_widths(ucd:&Ucd, text:&str, ambiguous_wide:bool) -> Vec<(u32, u32)> {
+    let mut result:Vec<(u32, u32)> = Vec::new();
+    // The index of the last visible character, the base of the cluster, across the zero width ones.
+    let mut base:Option<usize> = None;
+    let mut in_flag = false;
+    for chr in text.chars() {
+        let code_point = chr as u32;
+        let mut width = char_width(ucd, code_point, ambiguous_wide);
+        let after_zwj = result.last().is_some_and(|(previous, _)| *previous == ZERO_WIDTH_JOINER);
+        let base_width = base.map_or(0, |index| result[index].1);
+        if code_point == VARIATION_SELECTOR_16 {
+            if let Some(index) = base.filter(|index| has_emoji_property(ucd, result[*index].0, "Emoji")) {
+                result[index].1 = base_width.max(2);
+            }
+        } else if after_zwj && base_width == 2 {
+            // Part of the base glyph, which stays the base of the cluster: a VS16 after it is for it.
+            width = 0;
+        } else if base.is_some_and(|index| has_emoji_property(ucd, result[index].0, "Emoji_Modifier_Base"))
+                && has_emoji_property(ucd, code_point, "Emoji_Modifier") {
+            width = 0;
+        } else if is_regional_indicator(code_point) {
+            width = if in_flag { 0 } else { 2 };
+            in_flag = !in_flag;
+        }
+        if !is_regional_indicator(code_point) {
+            in_flag = false;
+        }
+        if width > 0 {
+            base = Some(result.len());
+        }
+        result.push((code_point, width));
+    }
+    result
+}
+
+/// `unicode_info width <text>`: the columns of each character, then of the text.
+pub fn print_width(ucd:&Ucd, text:&str, flags:&help::Flags) {
+    let widths = text_widths(ucd, text, flags.ambiguous_wide);
+    for (code_point, width) in &widths {
+        let fields = ucd.unicode_data(*code_point).unwrap_or_default();
+        let name = fields.get(1).copied().unwrap_or("<unassigned>");
+        let general_category = fields.get(2).copied().unwrap_or("Cn");
+        println!("{}  {}", width, char_table::char_line(*code_point, name, general_category, flags));
+    }
+    let total:u32 = widths.iter().map(|(_, width)| width).sum();
+    println!("{} {}", color_utils::label(&"Width:".to_string(), flags), total);
+}
+
+pub fn get_east_asian_width(ucd:&Ucd, code_point:u32, flags:&help::Flags) {
+    let label = color_utils::label(&"East Asian Width (ea):".to_string(), flags);
+    let Some(ea) = ucd.east_asian_width(code_point) else {
+        println!("  {} (no EastAsianWidth.txt)", label);
+        return;
+    };
+    match ucd.value_aliases("ea", ea) {
+        Some(aliases) if aliases.len() > 1 => println!("  {} {} ; {}", label, aliases[0], aliases[1]),
+        _ => println!("  {} {}", label, ea),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_widths() {
+        let ucd = Ucd::from_texts(&[
+            ("UnicodeData.txt", "0009;<control>;Cc;0;S;;;;;N;CHARACTER TABULATION;;;;\n\
+                    0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;\n\
+                    00E9;LATIN SMALL LETTER E WITH ACUTE;Ll;0;L;0065 0301;;;;N;;;00C9;;00C9\n\
+                    0301;COMBINING ACUTE ACCENT;Mn;230;NSM;;;;;N;NON-SPACING ACUTE;;;;\n\
+                    03B1;GREEK SMALL LETTER ALPHA;Ll;0;L;;;;;N;;;0391;;0391\n\
+                    200D;ZERO WIDTH JOINER;Cf;0;BN;;;;;N;;;;;\n\
+                    2764;HEAVY BLACK HEART;So;0;ON;;;;;N;;;;;\n\
+                    65E5;<CJK Ideograph>;Lo;0;L;;;;;N;;;;;\n\
+                    FE0F;VARIATION SELECTOR-16;Mn;0;NSM;;;;;N;;;;;\n\
+                    1F1EB;REGIONAL INDICATOR SYMBOL LETTER F;So;0;L;;;;;N;;;;;\n\
+                    1F1F7;REGIONAL INDICATOR SYMBOL LETTER R;So;0;L;;;;;N;;;;;\n\
+                    1F468;MAN;So;0;ON;;;;;N;;;;;\n\
+                    1F469;WOMAN;So;0;ON;;;;;N;;;;;\n\
+                    2695;STAFF OF AESCULAPIUS;So;0;ON;;;;;N;;;;;\n\
+                    1F308;RAINBOW;So;0;ON;;;;;N;;;;;\n\
+                    1F3F3;WAVING WHITE FLAG;So;0;ON;;;;;N;;;;;\n\
+                    1F3FD;EMOJI MODIFIER FITZPATRICK TYPE-4;Sk;0;ON;;;;;N;;;;;\n\
+                    1F44D;THUMBS UP SIGN;So;0;ON;;;;;N;;;;;\n\
+                    1F525;FIRE;So;0;ON;;;;;N;;;;;\n\
+                    1F9D1;ADULT;So;0;ON;;;;;N;;;;;\n"),
+            ("DerivedCoreProperties.txt", "200B..200F    ; Default_Ignorable_Code_Point # Cf   [5]\n\
+                    FE00..FE0F    ; Default_Ignorable_Code_Point # Mn  [16]\n"),
+            ("EastAsianWidth.txt", "0041 ; Na # Lu\n00E9 ; A # Ll\n03B1 ; A # Ll\n2764 ; N # So\n\
+                    4E00..9FFF ; W # Lo\n1F1E6..1F1FF ; N # So\n1F468..1F469 ; W # So\n2695 ; N # So\n1F308 ; W # So\n\
+                    1F3F3 ; N # So\n1F3FB..1F3FF ; W # Sk\n1F44D ; W # So\n1F525 ; W # So\n1F9D1 ; W # So\n"),
+            ("emoji-data.txt", "2764 ; Emoji # E0.6 [1]\n1F3F3 ; Emoji # E0.7 [1]\n\
+                    1F3FB..1F3FF ; Emoji_Modifier # E1.0 [5]\n1F44D ; Emoji_Modifier_Base # E0.6 [1]\n\
+                    1F9D1 ; Emoji_Modifier_Base # E5.0 [1]\n"),
+        ]);
+
+        let total = |text:&str, ambiguous_wide:bool| text_widths(&ucd, text, ambiguous_wide).iter().map(|(_, width)| width).sum::<u32>();
+        assert_eq!(total("A\u{65E5}", false), 3);
+        assert_eq!(total("e\u{301}\t", false), 1);
+        assert_eq!(total("\u{E9}\u{3B1}", false), 2);
+        assert_eq!(total("\u{E9}\u{3B1}", true), 4);
+        assert_eq!(total("\u{2764}", false), 1);
+        assert_eq!(total("\u{2764}\u{FE0F}", false), 2);
+        assert_eq!(total("A\u{FE0F}", false), 1);
+        assert_eq!(total("\u{1F1EB}\u{1F1F7}", false), 2);
+        assert_eq!(total("\u{1F1EB}\u{1F1F7}\u{1F1EB}", false), 4);
+        assert_eq!(total("\u{1F468}\u{200D}\u{1F469}", false), 2);
+        assert_eq!(total("A\u{200D}\u{65E5}", false), 3);
+        // thumbs up: medium skin tone
+        assert_eq!(total("\u{1F44D}\u{1F3FD}", false), 2);
+        // heart on fire
+        assert_eq!(total("\u{2764}\u{FE0F}\u{200D}\u{1F525}", false), 2);
+        // rainbow flag
+        assert_eq!(total("\u{1F3F3}\u{FE0F}\u{200D}\u{1F308}", false), 2);
+        // health worker
+        assert_eq!(total("\u{1F9D1}\u{200D}\u{2695}\u{FE0F}", false), 2);
+        assert_eq!(total("\u{1F9D1}\u{200D}\u{2695}\u{FE0F}A", false), 3);
+    }
+}